@@ -237,6 +237,57 @@ fn json_rust_parse(b: &mut Bencher) {
     });
 }
 
+#[bench]
+fn json_rust_parse_reusable(b: &mut Bencher) {
+    let mut parser = json::Parser::new();
+
+    b.bytes = JSON_STR.len() as u64;
+
+    b.iter(|| {
+        parser.parse(JSON_STR).unwrap();
+    });
+}
+
+#[bench]
+fn json_rust_parse_large_ascii(b: &mut Bencher) {
+    let mut source = String::from("[");
+
+    for i in 0..1000 {
+        if i != 0 {
+            source.push(',');
+        }
+        source.push_str(JSON_STR);
+    }
+
+    source.push(']');
+
+    b.bytes = source.len() as u64;
+
+    b.iter(|| {
+        json::parse(&source).unwrap();
+    });
+}
+
+#[bench]
+fn json_rust_parse_borrowed_large_ascii(b: &mut Bencher) {
+    let mut source = String::from("[");
+
+    for i in 0..1000 {
+        if i != 0 {
+            source.push(',');
+        }
+        source.push_str(JSON_STR);
+    }
+
+    source.push(']');
+
+    b.bytes = source.len() as u64;
+
+    b.iter(|| {
+        json::parse_borrowed(&source).unwrap();
+    });
+}
+
 #[bench]
 fn json_rust_parse_floats(b: &mut Bencher) {
     b.bytes = JSON_FLOAT_STR.len() as u64;
@@ -0,0 +1,162 @@
+use std::borrow::Cow;
+use std::ops::Index;
+
+use crate::number::Number;
+use crate::object::Object;
+use crate::value::JsonValue;
+
+static NULL: BorrowedValue<'static> = BorrowedValue::Null;
+
+/// A variant of `JsonValue` produced by `json::parse_borrowed`, that borrows
+/// strings directly from the source whenever possible.
+///
+/// Strings (and object keys) that contain no escape sequences are stored as
+/// `Cow::Borrowed` slices of the source, only strings that had to be
+/// unescaped are allocated as `Cow::Owned`. For documents dominated by
+/// simple string fields this avoids most of the allocations performed by
+/// `json::parse`.
+///
+/// Objects are stored as a vector of key value pairs in source order, so
+/// looking up a key is a linear search. Should a key appear more than once,
+/// lookups will find the last one, matching the behavior of `json::parse`.
+///
+/// ```
+/// # use std::borrow::Cow;
+/// let source = r#"{"plain":"foo","escaped":"b\u0061r"}"#;
+/// let data = json::parse_borrowed(source).unwrap();
+///
+/// assert_eq!(data["plain"].as_cow_str(), Some(&Cow::Borrowed("foo")));
+/// assert_eq!(data["escaped"].as_cow_str(), Some(&Cow::Owned("bar".to_string())));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum BorrowedValue<'a> {
+    Null,
+    String(Cow<'a, str>),
+    Number(Number),
+    Boolean(bool),
+    Object(Vec<(Cow<'a, str>, BorrowedValue<'a>)>),
+    Array(Vec<BorrowedValue<'a>>),
+}
+
+impl<'a> BorrowedValue<'a> {
+    pub fn is_null(&self) -> bool {
+        matches!(*self, BorrowedValue::Null)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            BorrowedValue::String(ref value) => Some(value),
+            _                                => None,
+        }
+    }
+
+    /// Obtain the underlying `Cow` of a string. Matching on it reveals
+    /// whether the string was borrowed from the source, in which case the
+    /// contained `&'a str` can outlive the `BorrowedValue` itself.
+    pub fn as_cow_str(&self) -> Option<&Cow<'a, str>> {
+        match *self {
+            BorrowedValue::String(ref value) => Some(value),
+            _                                => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<Number> {
+        match *self {
+            BorrowedValue::Number(value) => Some(value),
+            _                            => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        self.as_number().map(|value| value.into())
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            BorrowedValue::Boolean(value) => Some(value),
+            _                             => None,
+        }
+    }
+
+    /// Works on `BorrowedValue::Object` - get a reference to the value
+    /// behind `key`, if present.
+    pub fn get(&self, key: &str) -> Option<&BorrowedValue<'a>> {
+        match *self {
+            BorrowedValue::Object(ref entries) => {
+                entries.iter().rev().find(|entry| entry.0 == key).map(|entry| &entry.1)
+            },
+            _ => None,
+        }
+    }
+
+    /// Convert into a `JsonValue` that no longer borrows from the source,
+    /// allocating all strings that were borrowed. Of duplicate object keys,
+    /// the last one is kept, the same as with `json::parse`.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = {
+    ///     let source = String::from(r#"{"name":"foo","tags":["a","b"]}"#);
+    ///
+    ///     json::parse_borrowed(&source).unwrap().into_owned()
+    /// };
+    ///
+    /// assert_eq!(data, object!{ name: "foo", tags: ["a", "b"] });
+    /// # }
+    /// ```
+    pub fn into_owned(self) -> JsonValue {
+        match self {
+            BorrowedValue::Null           => JsonValue::Null,
+            BorrowedValue::String(string) => JsonValue::from(string),
+            BorrowedValue::Number(number) => JsonValue::Number(number),
+            BorrowedValue::Boolean(value) => JsonValue::Boolean(value),
+            BorrowedValue::Object(entries) => {
+                let mut object = Object::with_capacity(entries.len());
+
+                for (key, value) in entries {
+                    object.insert(&key, value.into_owned());
+                }
+
+                JsonValue::Object(object)
+            },
+            BorrowedValue::Array(vec) => {
+                JsonValue::Array(vec.into_iter().map(BorrowedValue::into_owned).collect())
+            },
+        }
+    }
+
+    /// Works on `BorrowedValue::Array` - returns an iterator over members.
+    /// Will return an empty iterator if called on non-array types.
+    pub fn members(&self) -> std::slice::Iter<'_, BorrowedValue<'a>> {
+        match *self {
+            BorrowedValue::Array(ref vec) => vec.iter(),
+            _                             => [].iter(),
+        }
+    }
+}
+
+impl<'a> Index<usize> for BorrowedValue<'a> {
+    type Output = BorrowedValue<'a>;
+
+    fn index(&self, index: usize) -> &BorrowedValue<'a> {
+        match *self {
+            BorrowedValue::Array(ref vec) => vec.get(index).unwrap_or(&NULL),
+            _ => &NULL
+        }
+    }
+}
+
+impl<'a> Index<&str> for BorrowedValue<'a> {
+    type Output = BorrowedValue<'a>;
+
+    fn index(&self, index: &str) -> &BorrowedValue<'a> {
+        self.get(index).unwrap_or(&NULL)
+    }
+}
+
+impl<'a> From<BorrowedValue<'a>> for JsonValue {
+    fn from(value: BorrowedValue<'a>) -> JsonValue {
+        value.into_owned()
+    }
+}
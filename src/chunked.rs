@@ -0,0 +1,311 @@
+// Parsing a document that arrives in multiple buffers, without joining them
+// first. Objects and arrays are walked byte by byte across chunk boundaries,
+// while every string, number or literal is gathered into a small scratch
+// buffer, so at most a single token is ever copied. Numbers and literals are
+// then read by the strict parser, so they behave exactly like in `parse`.
+
+use std::str;
+
+use crate::object::Object;
+use crate::{ JsonValue, Error, Result, parse, unescape_string };
+
+// How many nested Objects/Arrays are allowed to be parsed, same as strict
+const DEPTH_LIMIT: usize = 512;
+
+struct ChunkedParser<'a> {
+    chunks: &'a [&'a str],
+    chunk: usize,
+    index: usize,
+    line: usize,
+    column: usize,
+    depth: usize,
+    scratch: Vec<u8>,
+}
+
+impl<'a> ChunkedParser<'a> {
+    // Return the current byte without consuming it, moving on to the next
+    // chunk once the current one is exhausted.
+    fn current(&mut self) -> Option<u8> {
+        while let Some(chunk) = self.chunks.get(self.chunk) {
+            if let Some(&ch) = chunk.as_bytes().get(self.index) {
+                return Some(ch);
+            }
+
+            self.chunk += 1;
+            self.index = 0;
+        }
+
+        None
+    }
+
+    // Consume the current byte, must only follow a successful `current`.
+    fn bump(&mut self) {
+        match self.chunks[self.chunk].as_bytes()[self.index] {
+            b'\n' => {
+                self.line += 1;
+                self.column = 0;
+            },
+            // Continuation bytes of UTF-8 don't start a new column
+            ch if ch & 0xC0 != 0x80 => self.column += 1,
+            _ => {},
+        }
+
+        self.index += 1;
+    }
+
+    // Skip whitespace and return the next byte without consuming it.
+    fn peek(&mut self) -> Option<u8> {
+        while let Some(ch) = self.current() {
+            match ch {
+                9 ..= 13 | 32 => self.bump(),
+                _             => return Some(ch),
+            }
+        }
+
+        None
+    }
+
+    fn unexpected_character(&self) -> Error {
+        let ch = self.chunks[self.chunk][self.index..]
+                     .chars()
+                     .next()
+                     .expect("Must have a character");
+
+        Error::UnexpectedCharacter {
+            ch,
+            line: self.line,
+            column: self.column + 1,
+        }
+    }
+
+    // Errors from parsing a single token are relative to the token, which
+    // never spans multiple lines, so only the column needs to be moved.
+    fn relocate(error: Error, line: usize, column: usize) -> Error {
+        match error {
+            Error::UnexpectedCharacter { ch, column: offset, .. } => {
+                Error::UnexpectedCharacter {
+                    ch,
+                    line,
+                    column: column + offset,
+                }
+            },
+            error => error,
+        }
+    }
+
+    fn scratch_str(&self) -> Result<&str> {
+        str::from_utf8(&self.scratch).map_err(|_| Error::FailedUtf8Parsing)
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        match self.peek() {
+            Some(ch) if ch == byte => {
+                self.bump();
+                Ok(())
+            },
+            Some(_) => Err(self.unexpected_character()),
+            None    => Err(Error::UnexpectedEndOfJson),
+        }
+    }
+
+    // Called after a member of an object or an array, returns `false` once
+    // the container is closed.
+    fn expect_separator(&mut self, closing: u8) -> Result<bool> {
+        match self.peek() {
+            Some(b',') => {
+                self.bump();
+                Ok(true)
+            },
+            Some(ch) if ch == closing => {
+                self.bump();
+                Ok(false)
+            },
+            Some(_) => Err(self.unexpected_character()),
+            None    => Err(Error::UnexpectedEndOfJson),
+        }
+    }
+
+    fn enter(&mut self) -> Result<()> {
+        if self.depth == DEPTH_LIMIT {
+            return Err(Error::ExceededDepthLimit);
+        }
+
+        self.depth += 1;
+        self.bump();
+
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue> {
+        match self.peek() {
+            None       => Err(Error::UnexpectedEndOfJson),
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => self.parse_array(),
+            Some(b'"') => Ok(JsonValue::from(self.parse_string()?)),
+            Some(b',') |
+            Some(b']') |
+            Some(b'}') => Err(self.unexpected_character()),
+            Some(_)    => self.parse_primitive(),
+        }
+    }
+
+    // Called on the opening quote, returns the unescaped string.
+    fn parse_string(&mut self) -> Result<&str> {
+        let (line, column) = (self.line, self.column);
+        let mut escaped = false;
+
+        self.scratch.clear();
+        self.bump();
+
+        loop {
+            match self.current() {
+                None         => return Err(Error::UnexpectedEndOfJson),
+                Some(b'"')   => break,
+                Some(0 ..= 0x1F) => return Err(self.unexpected_character()),
+                Some(b'\\')  => {
+                    escaped = true;
+                    self.scratch.push(b'\\');
+                    self.bump();
+
+                    match self.current() {
+                        Some(ch) => self.scratch.push(ch),
+                        None     => return Err(Error::UnexpectedEndOfJson),
+                    }
+                },
+                Some(ch)     => self.scratch.push(ch),
+            }
+
+            self.bump();
+        }
+
+        self.bump();
+
+        if escaped {
+            let literal = format!("\"{}\"", self.scratch_str()?);
+            let string = unescape_string(&literal)
+                             .map_err(|error| Self::relocate(error, line, column))?;
+
+            self.scratch = string.into_bytes();
+        }
+
+        self.scratch_str()
+    }
+
+    // Numbers and literals run until the next delimiter.
+    fn parse_primitive(&mut self) -> Result<JsonValue> {
+        let (line, column) = (self.line, self.column);
+
+        self.scratch.clear();
+
+        while let Some(ch) = self.current() {
+            match ch {
+                b',' | b']' | b'}' | 9 ..= 13 | 32 => break,
+                _ => {
+                    self.scratch.push(ch);
+                    self.bump();
+                },
+            }
+        }
+
+        let result = parse(self.scratch_str()?);
+
+        match result {
+            // Truncated by a delimiter, such as `tru]`, rather than the end
+            Err(Error::UnexpectedEndOfJson) if self.current().is_some() => {
+                Err(self.unexpected_character())
+            },
+            result => result.map_err(|error| Self::relocate(error, line, column)),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue> {
+        self.enter()?;
+
+        let mut object = Object::new();
+        let mut key = String::new();
+
+        if self.peek() == Some(b'}') {
+            self.bump();
+        } else {
+            loop {
+                match self.peek() {
+                    Some(b'"') => {},
+                    Some(_)    => return Err(self.unexpected_character()),
+                    None       => return Err(Error::UnexpectedEndOfJson),
+                }
+
+                key.clear();
+                key.push_str(self.parse_string()?);
+
+                self.expect(b':')?;
+
+                let value = self.parse_value()?;
+
+                object.insert(&key, value);
+
+                if !self.expect_separator(b'}')? {
+                    break;
+                }
+            }
+        }
+
+        self.depth -= 1;
+
+        Ok(JsonValue::Object(object))
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue> {
+        self.enter()?;
+
+        let mut array = Vec::new();
+
+        if self.peek() == Some(b']') {
+            self.bump();
+        } else {
+            loop {
+                array.push(self.parse_value()?);
+
+                if !self.expect_separator(b']')? {
+                    break;
+                }
+            }
+        }
+
+        self.depth -= 1;
+
+        Ok(JsonValue::Array(array))
+    }
+}
+
+/// Parse a JSON document that is split across multiple buffers, such as
+/// consecutive reads from a socket, without first joining them into a single
+/// `String`. The chunks are read in order as if they were concatenated, and
+/// can be split anywhere, including in the middle of a string or a number.
+///
+/// The result is the same as for `json::parse` on the joined source. Since
+/// `&str` chunks always end on a character boundary, so does every split.
+///
+/// ```
+/// let data = json::parse_chunks(&[r#"{ "name": "Ja"#, r#"ne", "age": 3"#, "0 }"]).unwrap();
+///
+/// assert_eq!(data["name"], "Jane");
+/// assert_eq!(data["age"], 30);
+/// ```
+pub fn parse_chunks(chunks: &[&str]) -> Result<JsonValue> {
+    let mut parser = ChunkedParser {
+        chunks,
+        chunk: 0,
+        index: 0,
+        line: 1,
+        column: 0,
+        depth: 0,
+        scratch: Vec::new(),
+    };
+
+    let value = parser.parse_value()?;
+
+    match parser.peek() {
+        Some(_) => Err(parser.unexpected_character()),
+        None    => Ok(value),
+    }
+}
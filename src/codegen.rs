@@ -147,6 +147,7 @@ pub trait Generator {
     fn write_json(&mut self, json: &JsonValue) -> io::Result<()> {
         match *json {
             JsonValue::Null               => self.write(b"null"),
+            JsonValue::Short(ref short) if short.is_raw_number() => self.write(short.as_bytes()),
             JsonValue::Short(ref short)   => self.write_string(short.as_str()),
             JsonValue::String(ref string) => self.write_string(string),
             JsonValue::Number(ref number) => self.write_number(number),
@@ -182,6 +183,45 @@ pub trait Generator {
     }
 }
 
+/// Escape a string for JSON, returning it as a quoted string literal, exactly
+/// as it would be written out when serializing a `JsonValue`.
+///
+/// ```
+/// assert_eq!(json::escape_string("say \"hi\"\n"), r#""say \"hi\"\n""#);
+/// ```
+pub fn escape_string(string: &str) -> String {
+    let mut gen = DumpGenerator::new();
+    gen.write_string(string).expect("Can't fail");
+    gen.consume()
+}
+
+/// Write values as newline delimited JSON (NDJSON, also known as JSON Lines),
+/// each value serialized compactly on a line of its own, terminated with
+/// `\n`. Line breaks inside of strings are always escaped, so every value
+/// takes up exactly one line.
+///
+/// ```
+/// # #[macro_use] extern crate json;
+/// # fn main() {
+/// let mut buf = Vec::new();
+///
+/// json::write_ndjson(&mut buf, vec![object!{ id: 1 }, array!["a\nb"]]).unwrap();
+///
+/// assert_eq!(String::from_utf8(buf).unwrap(), "{\"id\":1}\n[\"a\\nb\"]\n");
+/// # }
+/// ```
+pub fn write_ndjson<W, I>(writer: &mut W, values: I) -> io::Result<()>
+where W: Write, I: IntoIterator<Item = JsonValue> {
+    let mut gen = WriterGenerator::new(writer);
+
+    for value in values {
+        gen.write_json(&value)?;
+        gen.write_char(b'\n')?;
+    }
+
+    Ok(())
+}
+
 /// In-Memory Generator, this uses a Vec to store the JSON result.
 pub struct DumpGenerator {
     code: Vec<u8>,
@@ -16,12 +16,76 @@ pub enum Error {
     ExceededDepthLimit,
     FailedUtf8Parsing,
     WrongType(String),
+    UndefinedField(String),
+    IntegerOverflow(String),
+    IntegerOutOfRange(String),
+    ArrayIndexOutOfBounds {
+        index: usize,
+        len: usize,
+    },
+    ExceededKeyLimit(usize),
+    ExceededNodeLimit(usize),
+    NonFiniteNumber,
+    TrailingData {
+        offset: usize,
+    },
+    PatternMismatch {
+        pointer: String,
+        expected: String,
+        found: String,
+    },
+    ErrorResponse(String),
+    InvalidSyntax {
+        line: usize,
+        expected: String,
+    },
+    EmptyArray,
+    InvalidArgument(String),
+    Context {
+        path: String,
+        error: Box<Error>,
+    },
 }
 
 impl Error {
     pub fn wrong_type(expected: &str) -> Self {
         Error::WrongType(expected.into())
     }
+
+    pub fn undefined(field: &str) -> Self {
+        Error::UndefinedField(field.into())
+    }
+
+    pub fn invalid_argument(message: &str) -> Self {
+        Error::InvalidArgument(message.into())
+    }
+
+    /// Attach a path, or any other context, to an error, so that it's known
+    /// where it happened. Context is accumulated as the error bubbles up,
+    /// each call prepends to the path, separated by a `.` unless the path so
+    /// far starts with `[`.
+    ///
+    /// ```
+    /// let error = json::Error::wrong_type("Number").context("[0]").context("a");
+    ///
+    /// assert_eq!(error.to_string(), "a[0]: Wrong type, expected: Number");
+    /// ```
+    pub fn context(self, path: &str) -> Self {
+        match self {
+            Error::Context { path: inner, error } => {
+                let separator = if inner.starts_with('[') { "" } else { "." };
+
+                Error::Context {
+                    path: format!("{}{}{}", path, separator, inner),
+                    error,
+                }
+            },
+            error => Error::Context {
+                path: path.into(),
+                error: Box::new(error),
+            },
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -35,10 +99,46 @@ impl fmt::Display for Error {
                 ref column,
             } => write!(f, "Unexpected character: {} at ({}:{})", ch, line, column),
 
-            UnexpectedEndOfJson   => write!(f, "Unexpected end of JSON"),
-            ExceededDepthLimit    => write!(f, "Exceeded depth limit"),
-            FailedUtf8Parsing     => write!(f, "Failed to parse UTF-8 bytes"),
-            WrongType(ref s)      => write!(f, "Wrong type, expected: {}", s),
+            UnexpectedEndOfJson      => write!(f, "Unexpected end of JSON"),
+            ExceededDepthLimit       => write!(f, "Exceeded depth limit"),
+            FailedUtf8Parsing        => write!(f, "Failed to parse UTF-8 bytes"),
+            WrongType(ref s)         => write!(f, "Wrong type, expected: {}", s),
+            UndefinedField(ref s)    => write!(f, "Undefined field: {}", s),
+            IntegerOverflow(ref s)   => write!(f, "Integer {} can't be represented exactly", s),
+            IntegerOutOfRange(ref s) => write!(f, "Number {} is not an integer in range", s),
+            ExceededKeyLimit(ref n)  => write!(f, "Exceeded limit of {} keys in an object", n),
+            ExceededNodeLimit(ref n) => write!(f, "Exceeded limit of {} values", n),
+            NonFiniteNumber          => write!(f, "Number is NaN or infinite"),
+
+            ArrayIndexOutOfBounds {
+                ref index,
+                ref len,
+            } => write!(f, "Array index {} out of bounds for length {}", index, len),
+
+            TrailingData {
+                ref offset,
+            } => write!(f, "Unexpected data after the end of JSON at byte {}", offset),
+
+            PatternMismatch {
+                ref pointer,
+                ref expected,
+                ref found,
+            } => write!(f, "Mismatch at {:?}, expected: {}, found: {}", pointer, expected, found),
+
+            ErrorResponse(ref s)     => write!(f, "Error response: {}", s),
+
+            InvalidSyntax {
+                ref line,
+                ref expected,
+            } => write!(f, "Invalid syntax at line {}, expected: {}", line, expected),
+
+            EmptyArray               => write!(f, "Array is empty"),
+            InvalidArgument(ref s)   => write!(f, "Invalid argument: {}", s),
+
+            Context {
+                ref path,
+                ref error,
+            } => write!(f, "{}: {}", path, error),
         }
     }
 }
@@ -53,6 +153,27 @@ impl error::Error for Error {
             ExceededDepthLimit         => "Exceeded depth limit",
             FailedUtf8Parsing          => "Failed to read bytes as UTF-8 from JSON",
             WrongType(_)               => "Wrong type",
+            UndefinedField(_)          => "Undefined field",
+            IntegerOverflow(_)         => "Integer exceeds the exact range of f64",
+            IntegerOutOfRange(_)       => "Number is not an integer in range",
+            ArrayIndexOutOfBounds { .. } => "Array index out of bounds",
+            ExceededKeyLimit(_)        => "Exceeded key limit",
+            ExceededNodeLimit(_)       => "Exceeded node limit",
+            NonFiniteNumber            => "Number is NaN or infinite",
+            TrailingData { .. }        => "Unexpected data after the end of JSON",
+            PatternMismatch { .. }     => "Value doesn't match the pattern",
+            ErrorResponse(_)           => "Error response",
+            InvalidSyntax { .. }       => "Invalid syntax",
+            EmptyArray                 => "Array is empty",
+            InvalidArgument(_)         => "Invalid argument",
+            Context { .. }             => "Error with context",
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Context { ref error, .. } => Some(&**error),
+            _                                => None,
         }
     }
 }
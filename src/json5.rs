@@ -0,0 +1,342 @@
+// A relaxed parser for configuration files, accepting a subset of JSON5 on
+// top of regular JSON. Unlike the strict parser in `parser.rs`, this one
+// doesn't try to be fast, it's a plain recursive descent over the bytes of
+// the source, so that none of the extensions slow down `json::parse`.
+
+use std::char::decode_utf16;
+
+use crate::object::Object;
+use crate::parser::unexpected_character_at;
+use crate::{ JsonValue, Error, Result };
+
+// How many nested Objects/Arrays are allowed to be parsed, same as strict
+const DEPTH_LIMIT: usize = 512;
+
+struct Json5Parser<'a> {
+    source: &'a str,
+    bytes: &'a [u8],
+    index: usize,
+    depth: usize,
+}
+
+impl<'a> Json5Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Json5Parser {
+            source,
+            bytes: source.as_bytes(),
+            index: 0,
+            depth: 0,
+        }
+    }
+
+    fn unexpected_character<T>(&self, at: usize) -> Result<T> {
+        Err(unexpected_character_at(self.source, at))
+    }
+
+    // Skip whitespace, as well as `//` line comments and `/* */` block
+    // comments.
+    fn skip_whitespace(&mut self) -> Result<()> {
+        while let Some(&ch) = self.bytes.get(self.index) {
+            match ch {
+                9 ..= 13 | 32 => self.index += 1,
+                b'/' => match self.bytes.get(self.index + 1) {
+                    Some(b'/') => {
+                        while self.index < self.bytes.len() && self.bytes[self.index] != b'\n' {
+                            self.index += 1;
+                        }
+                    },
+                    Some(b'*') => {
+                        match self.source[self.index + 2..].find("*/") {
+                            Some(end) => self.index += end + 4,
+                            None      => return Err(Error::UnexpectedEndOfJson),
+                        }
+                    },
+                    _ => return self.unexpected_character(self.index),
+                },
+                _ => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    // Skip whitespace and return the next byte without consuming it.
+    fn peek(&mut self) -> Result<u8> {
+        self.skip_whitespace()?;
+
+        match self.bytes.get(self.index) {
+            Some(&ch) => Ok(ch),
+            None      => Err(Error::UnexpectedEndOfJson),
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        if self.peek()? != byte {
+            return self.unexpected_character(self.index);
+        }
+
+        self.index += 1;
+
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue> {
+        match self.peek()? {
+            b'{' => self.parse_object(),
+            b'[' => self.parse_array(),
+            quote @ b'"' | quote @ b'\'' => {
+                self.index += 1;
+
+                Ok(self.parse_string(quote)?.into())
+            },
+            b'-' | b'0' ..= b'9' => self.parse_number(),
+            _ => {
+                let start = self.index;
+
+                match self.parse_identifier()? {
+                    "true"  => Ok(JsonValue::Boolean(true)),
+                    "false" => Ok(JsonValue::Boolean(false)),
+                    "null"  => Ok(JsonValue::Null),
+                    _       => self.unexpected_character(start),
+                }
+            }
+        }
+    }
+
+    fn enter(&mut self) -> Result<()> {
+        if self.depth == DEPTH_LIMIT {
+            return Err(Error::ExceededDepthLimit);
+        }
+
+        self.depth += 1;
+        self.index += 1;
+
+        Ok(())
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue> {
+        self.enter()?;
+
+        let mut object = Object::new();
+
+        loop {
+            let key = match self.peek()? {
+                b'}' => break,
+                quote @ b'"' | quote @ b'\'' => {
+                    self.index += 1;
+                    self.parse_string(quote)?
+                },
+                _ => self.parse_identifier()?.to_string(),
+            };
+
+            self.expect(b':')?;
+
+            let value = self.parse_value()?;
+
+            object.insert(&key, value);
+
+            match self.peek()? {
+                b',' => self.index += 1,
+                b'}' => break,
+                _    => return self.unexpected_character(self.index),
+            }
+        }
+
+        self.index += 1;
+        self.depth -= 1;
+
+        Ok(JsonValue::Object(object))
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue> {
+        self.enter()?;
+
+        let mut array = Vec::new();
+
+        loop {
+            if self.peek()? == b']' {
+                break;
+            }
+
+            array.push(self.parse_value()?);
+
+            match self.peek()? {
+                b',' => self.index += 1,
+                b']' => break,
+                _    => return self.unexpected_character(self.index),
+            }
+        }
+
+        self.index += 1;
+        self.depth -= 1;
+
+        Ok(JsonValue::Array(array))
+    }
+
+    // Unquoted keys follow the ECMAScript rules for identifiers, limited
+    // to ASCII: letters, digits, `_` and `$`, not starting with a digit.
+    fn parse_identifier(&mut self) -> Result<&'a str> {
+        let start = self.index;
+
+        while let Some(&ch) = self.bytes.get(self.index) {
+            match ch {
+                b'a' ..= b'z' | b'A' ..= b'Z' | b'_' | b'$' => {},
+                b'0' ..= b'9' if self.index > start => {},
+                _ => break,
+            }
+
+            self.index += 1;
+        }
+
+        if self.index == start {
+            return self.unexpected_character(start);
+        }
+
+        Ok(&self.source[start..self.index])
+    }
+
+    // Numbers are strict JSON numbers, so the strict parser can deal with
+    // them, its errors only have to be moved to the right position.
+    fn parse_number(&mut self) -> Result<JsonValue> {
+        let start = self.index;
+
+        let (value, rest) = match crate::parse_partial(&self.source[start..]) {
+            Ok(result) => result,
+            Err(Error::UnexpectedCharacter { column, .. }) => {
+                return self.unexpected_character(start + column - 1);
+            },
+            Err(error) => return Err(error),
+        };
+
+        self.index = self.source.len() - rest.len();
+
+        Ok(value)
+    }
+
+    // Called with the opening quote already consumed, the string ends at
+    // the next unescaped `quote`.
+    fn parse_string(&mut self, quote: u8) -> Result<String> {
+        let mut string = String::new();
+        let mut start = self.index;
+
+        loop {
+            let ch = match self.bytes.get(self.index) {
+                Some(&ch) => ch,
+                None      => return Err(Error::UnexpectedEndOfJson),
+            };
+
+            if ch == quote {
+                string.push_str(&self.source[start..self.index]);
+                self.index += 1;
+
+                return Ok(string);
+            }
+
+            match ch {
+                b'\\' => {
+                    string.push_str(&self.source[start..self.index]);
+                    self.index += 1;
+                    self.parse_escape(&mut string)?;
+                    start = self.index;
+                },
+                0 ..= 0x1F => return self.unexpected_character(self.index),
+                _ => self.index += 1,
+            }
+        }
+    }
+
+    // Called with the backslash already consumed
+    fn parse_escape(&mut self, string: &mut String) -> Result<()> {
+        let ch = match self.bytes.get(self.index) {
+            Some(&ch) => ch,
+            None      => return Err(Error::UnexpectedEndOfJson),
+        };
+
+        self.index += 1;
+
+        let escaped = match ch {
+            b'"' | b'\'' | b'\\' | b'/' => ch as char,
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let mut units = vec![self.parse_hex()?];
+
+                // A high surrogate has to be followed by an escaped low one
+                if (0xD800 ..= 0xDBFF).contains(&units[0]) && self.source[self.index..].starts_with("\\u") {
+                    self.index += 2;
+                    units.push(self.parse_hex()?);
+                }
+
+                for ch in decode_utf16(units) {
+                    match ch {
+                        Ok(ch) => string.push(ch),
+                        Err(_) => return Err(Error::FailedUtf8Parsing),
+                    }
+                }
+
+                return Ok(());
+            },
+            _ => return self.unexpected_character(self.index - 1),
+        };
+
+        string.push(escaped);
+
+        Ok(())
+    }
+
+    fn parse_hex(&mut self) -> Result<u16> {
+        let mut code = 0;
+
+        for _ in 0..4 {
+            let ch = match self.bytes.get(self.index) {
+                Some(&ch) => ch,
+                None      => return Err(Error::UnexpectedEndOfJson),
+            };
+
+            let digit = match (ch as char).to_digit(16) {
+                Some(digit) => digit as u16,
+                None        => return self.unexpected_character(self.index),
+            };
+
+            code = code << 4 | digit;
+            self.index += 1;
+        }
+
+        Ok(code)
+    }
+}
+
+/// Parse a configuration file written in a relaxed, JSON5 flavored syntax.
+/// On top of regular JSON, the following extensions are accepted:
+///
+/// - `// line` and `/* block */` comments
+/// - trailing commas in objects and arrays
+/// - unquoted object keys, as long as they are valid identifiers
+/// - strings and keys in single quotes, where `\'` is a valid escape
+///
+/// Other JSON5 extensions, such as hexadecimal numbers or multi-line
+/// strings, are not supported. `json::parse` never accepts any of the above.
+///
+/// ```
+/// # #[macro_use] extern crate json;
+/// # fn main() {
+/// let data = json::parse_json5("{ key: 'value', other: 1, } // done").unwrap();
+///
+/// assert_eq!(data, object!{ key: "value", other: 1 });
+/// # }
+/// ```
+pub fn parse_json5(source: &str) -> Result<JsonValue> {
+    let mut parser = Json5Parser::new(source);
+    let value = parser.parse_value()?;
+
+    parser.skip_whitespace()?;
+
+    if parser.index < source.len() {
+        return parser.unexpected_character(parser.index);
+    }
+
+    Ok(value)
+}
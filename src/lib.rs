@@ -202,6 +202,11 @@ use std::result;
 pub mod codegen;
 mod parser;
 mod value;
+mod borrowed;
+mod json5;
+mod chunked;
+mod location;
+mod recover;
 mod error;
 mod util;
 
@@ -211,6 +216,9 @@ pub mod number;
 
 pub use error::Error;
 pub use value::JsonValue;
+pub use value::ArrayMergeStrategy;
+pub use value::FrozenValue;
+pub use borrowed::BorrowedValue;
 pub use value::JsonValue::Null;
 
 /// Result type used by this crate.
@@ -240,7 +248,29 @@ pub use Error as JsonError;
 #[deprecated(since="0.9.0", note="use `json::Result` instead")]
 pub use crate::Result as JsonResult;
 
-pub use parser::parse;
+pub use parser::{
+    parse,
+    parse_partial,
+    parse_exact_integers,
+    parse_with_dedup_callback,
+    parse_with_reviver,
+    parse_preserve_numbers,
+    parse_lenient_numbers,
+    parse_relaxed_numbers,
+    parse_with_limits,
+    parse_reject_trailing,
+    parse_borrowed,
+    parse_array_streaming,
+    parse_object_streaming,
+    extract_pointer,
+    unescape_string,
+    Parser,
+};
+pub use codegen::{ escape_string, write_ndjson };
+pub use json5::parse_json5;
+pub use chunked::parse_chunks;
+pub use location::{ parse_with_location_map, LocationMap };
+pub use recover::parse_recover;
 
 pub type Array = Vec<JsonValue>;
 
@@ -249,6 +279,109 @@ pub fn from<T>(value: T) -> JsonValue where T: Into<JsonValue> {
     value.into()
 }
 
+/// Build a `JsonValue::Object` out of key value pairs. This is a lightweight
+/// alternative to the `object!` macro, handy when the pairs are already at
+/// hand as an array or an iterator.
+///
+/// ```
+/// let data = json::into_object([
+///     ("name", "John Doe".into()),
+///     ("tags", json::into_array(vec!["a".into(), 42.into()])),
+/// ]);
+///
+/// assert_eq!(data.dump(), r#"{"name":"John Doe","tags":["a",42]}"#);
+/// ```
+pub fn into_object<I>(pairs: I) -> JsonValue
+where I: IntoIterator<Item = (&'static str, JsonValue)> {
+    JsonValue::Object(pairs.into_iter().collect())
+}
+
+/// Build a `JsonValue::Array` out of values. See `into_object`.
+pub fn into_array<I>(items: I) -> JsonValue
+where I: IntoIterator<Item = JsonValue> {
+    JsonValue::Array(items.into_iter().collect())
+}
+
+/// Concatenate a sequence of `JsonValue::Array`s into a single array,
+/// preserving order. Errors if any of the values is not an array, mentioning
+/// the index of the offending value.
+///
+/// ```
+/// # #[macro_use] extern crate json;
+/// # fn main() {
+/// let data = json::array_concat(vec![array![1, 2], array![], array![3]]).unwrap();
+///
+/// assert_eq!(data, array![1, 2, 3]);
+/// # }
+/// ```
+pub fn array_concat<I>(values: I) -> Result<JsonValue>
+where I: IntoIterator<Item = JsonValue> {
+    let mut result = Vec::new();
+
+    for (index, value) in values.into_iter().enumerate() {
+        match value {
+            JsonValue::Array(mut vec) => result.append(&mut vec),
+            _ => return Err(Error::wrong_type(&format!("Array at index {}", index))),
+        }
+    }
+
+    Ok(JsonValue::Array(result))
+}
+
+/// Convert a `snake_case` key into `camelCase`, for use with
+/// `JsonValue::rename_keys_with`. Letters following an `_` are uppercased
+/// and the underscores dropped, leading underscores are kept as they are.
+///
+/// ```
+/// assert_eq!(json::snake_to_camel_case("user_id"), "userId");
+/// assert_eq!(json::snake_to_camel_case("_private_key"), "_privateKey");
+/// ```
+pub fn snake_to_camel_case(key: &str) -> String {
+    let trimmed = key.trim_start_matches('_');
+    let mut result = String::with_capacity(key.len());
+    let mut upper = false;
+
+    result.push_str(&key[..key.len() - trimmed.len()]);
+
+    for ch in trimmed.chars() {
+        if ch == '_' {
+            upper = true;
+        } else if upper {
+            result.extend(ch.to_uppercase());
+            upper = false;
+        } else {
+            result.push(ch);
+        }
+    }
+
+    result
+}
+
+/// Convert a `camelCase` key into `snake_case`, the reverse of
+/// `snake_to_camel_case`. Every uppercase letter is lowercased and prefixed
+/// with an `_`, unless it's at the start of the key.
+///
+/// ```
+/// assert_eq!(json::camel_to_snake_case("userId"), "user_id");
+/// assert_eq!(json::camel_to_snake_case("HTTPCode"), "h_t_t_p_code");
+/// ```
+pub fn camel_to_snake_case(key: &str) -> String {
+    let mut result = String::with_capacity(key.len() + 4);
+
+    for ch in key.chars() {
+        if ch.is_uppercase() {
+            if !result.is_empty() {
+                result.push('_');
+            }
+            result.extend(ch.to_lowercase());
+        } else {
+            result.push(ch);
+        }
+    }
+
+    result
+}
+
 /// Pretty prints out the value as JSON string.
 pub fn stringify<T>(root: T) -> String where T: Into<JsonValue> {
     let root: JsonValue = root.into();
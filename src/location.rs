@@ -0,0 +1,200 @@
+// Source spans for tooling. The document is parsed by the regular parser
+// first, so that errors are reported the same way, and only once it's known
+// to be valid JSON it is scanned a second time to record where every value
+// starts and ends. This keeps the bookkeeping out of the hot parsing loop.
+
+use std::collections::BTreeMap;
+
+use crate::value::pointer::escape_token;
+use crate::{ JsonValue, Result, parse, unescape_string };
+
+/// Byte ranges of values in the source, keyed by JSON Pointer, as returned
+/// by `json::parse_with_location_map`.
+pub type LocationMap = BTreeMap<String, (usize, usize)>;
+
+struct SpanScanner<'a> {
+    source: &'a str,
+    bytes: &'a [u8],
+    index: usize,
+    spans: LocationMap,
+}
+
+impl<'a> SpanScanner<'a> {
+    fn skip_whitespace(&mut self) {
+        while let Some(9 ..= 13) | Some(32) = self.bytes.get(self.index) {
+            self.index += 1;
+        }
+    }
+
+    // Called on the opening quote, returns the string as it would be parsed.
+    fn scan_string(&mut self) -> String {
+        let start = self.index;
+        let mut escaped = false;
+
+        self.index += 1;
+
+        loop {
+            match self.bytes[self.index] {
+                b'"'  => break,
+                b'\\' => {
+                    escaped = true;
+                    self.index += 1;
+                },
+                _ => {},
+            }
+
+            self.index += 1;
+        }
+
+        self.index += 1;
+
+        let literal = &self.source[start..self.index];
+
+        if escaped {
+            unescape_string(literal).expect("Source was already parsed")
+        } else {
+            literal[1..literal.len() - 1].to_owned()
+        }
+    }
+
+    // Drop the spans of everything nested under `pointer`.
+    fn remove_descendants(&mut self, pointer: &str) {
+        let prefix = format!("{}/", pointer);
+        let nested: Vec<String> = self.spans
+                                      .range(prefix.clone()..)
+                                      .map(|(key, _)| key)
+                                      .take_while(|key| key.starts_with(&prefix))
+                                      .cloned()
+                                      .collect();
+
+        for key in nested {
+            self.spans.remove(&key);
+        }
+    }
+
+    // The source is known to be valid, so every byte that isn't part of
+    // an object, array or string belongs to a number or a literal until
+    // the next delimiter.
+    fn scan_value(&mut self, pointer: &mut String) {
+        self.skip_whitespace();
+
+        let start = self.index;
+
+        match self.bytes[self.index] {
+            b'{' => {
+                self.index += 1;
+
+                loop {
+                    self.skip_whitespace();
+
+                    match self.bytes[self.index] {
+                        b'}' => break,
+                        b',' => {
+                            self.index += 1;
+                            continue;
+                        },
+                        _ => {},
+                    }
+
+                    let key = self.scan_string();
+
+                    self.skip_whitespace();
+                    self.index += 1; // `:`
+
+                    let len = pointer.len();
+
+                    pointer.push('/');
+                    escape_token(pointer, &key);
+
+                    // A duplicate key replaces the whole earlier value
+                    if self.spans.remove(pointer.as_str()).is_some() {
+                        self.remove_descendants(pointer);
+                    }
+
+                    self.scan_value(pointer);
+                    pointer.truncate(len);
+                }
+
+                self.index += 1;
+            },
+            b'[' => {
+                self.index += 1;
+
+                let mut member = 0;
+
+                loop {
+                    self.skip_whitespace();
+
+                    match self.bytes[self.index] {
+                        b']' => break,
+                        b',' => {
+                            self.index += 1;
+                            continue;
+                        },
+                        _ => {},
+                    }
+
+                    let len = pointer.len();
+
+                    pointer.push('/');
+                    pointer.push_str(&member.to_string());
+                    self.scan_value(pointer);
+                    pointer.truncate(len);
+
+                    member += 1;
+                }
+
+                self.index += 1;
+            },
+            b'"' => {
+                self.scan_string();
+            },
+            _ => {
+                while let Some(&ch) = self.bytes.get(self.index) {
+                    match ch {
+                        b',' | b']' | b'}' | 9 ..= 13 | 32 => break,
+                        _ => self.index += 1,
+                    }
+                }
+            },
+        }
+
+        self.spans.insert(pointer.clone(), (start, self.index));
+    }
+}
+
+/// Parse a JSON document, and alongside the `JsonValue` return a map of
+/// every value in it, keyed by its JSON Pointer (`""` being the root), to
+/// the `(start, end)` byte range it spans in the source. The end is
+/// exclusive, so `&source[start..end]` is the value as written, quotes and
+/// brackets included. This makes it possible to map a cursor position in an
+/// editor back to a node in the tree.
+///
+/// Object keys that appear more than once map to the span of the last one,
+/// which is also the one kept by the parser, and nothing nested in the
+/// values they replaced is kept in the map. Errors are the same as for
+/// `json::parse`.
+///
+/// ```
+/// let source = r#"{ "user": { "name": "Jane" } }"#;
+/// let (data, spans) = json::parse_with_location_map(source).unwrap();
+///
+/// let (start, end) = spans["/user/name"];
+///
+/// assert_eq!(&source[start..end], r#""Jane""#);
+/// assert_eq!(data["user"]["name"], "Jane");
+/// ```
+pub fn parse_with_location_map(source: &str) -> Result<(JsonValue, LocationMap)> {
+    let value = parse(source)?;
+
+    let mut scanner = SpanScanner {
+        source,
+        bytes: source.as_bytes(),
+        index: 0,
+        spans: LocationMap::new(),
+    };
+
+    scanner.scan_value(&mut String::new());
+
+    Ok((value, scanner.spans))
+}
@@ -1,4 +1,6 @@
-use std::{ ops, fmt, f32, f64 };
+use std::{ ops, fmt, f32, f64, str };
+use std::io::Write;
+use std::cmp::Ordering;
 use std::convert::{TryFrom, Infallible};
 use std::num::{FpCategory, TryFromIntError};
 use crate::util::grisu2;
@@ -11,6 +13,14 @@ pub const NAN: Number = Number {
     exponent: 0
 };
 
+// Stand-in for infinity, the largest number that can be represented, which
+// is well out of range for f64 and f32, so it converts to infinity.
+pub(crate) const INFINITY: Number = Number {
+    category: POSITIVE,
+    mantissa: 1,
+    exponent: i16::MAX
+};
+
 const NEGATIVE: u8 = 0;
 const POSITIVE: u8 = 1;
 const NAN_MASK: u8 = !1;
@@ -126,6 +136,42 @@ impl Number {
         self.mantissa == 0 || self.is_nan()
     }
 
+    /// Compare two numbers exactly, without converting them to `f64`.
+    /// Positive and negative zero are equal, NaN is greater than all other
+    /// numbers and equal to itself, same as with `PartialEq`.
+    ///
+    /// ```
+    /// # use json::number::Number;
+    /// # use std::cmp::Ordering;
+    /// assert_eq!(Number::from(1.5).total_cmp(&Number::from(15)), Ordering::Less);
+    /// assert_eq!(Number::from(-0.0).total_cmp(&Number::from(0)), Ordering::Equal);
+    /// assert_eq!(Number::from(f64::NAN).total_cmp(&Number::from(1e300)), Ordering::Greater);
+    /// ```
+    pub fn total_cmp(&self, other: &Number) -> Ordering {
+        match (self.is_nan(), other.is_nan()) {
+            (true, true)  => return Ordering::Equal,
+            (true, false) => return Ordering::Greater,
+            (false, true) => return Ordering::Less,
+            _             => {}
+        }
+
+        let sign = |num: &Number| match (num.is_zero(), num.is_sign_positive()) {
+            (true, _)      => 0,
+            (false, true)  => 1,
+            (false, false) => -1,
+        };
+
+        let (a, b) = (sign(self), sign(other));
+
+        if a != b || a == 0 {
+            return a.cmp(&b);
+        }
+
+        let ordering = cmp_magnitude(self.mantissa, self.exponent, other.mantissa, other.exponent);
+
+        if a > 0 { ordering } else { ordering.reverse() }
+    }
+
     /// Obtain an integer at a fixed decimal point. This is useful for
     /// converting monetary values and doing arithmetic on them without
     /// rounding errors introduced by floating point operations.
@@ -204,7 +250,13 @@ impl PartialEq for Number {
             return false;
         }
 
-        let e_diff = self.exponent - other.exponent;
+        // Exponents can be more than `i16::MAX` apart, and a non-zero
+        // mantissa scaled by 10^20 or more can't match one that fits `u64`
+        let e_diff = self.exponent as i32 - other.exponent as i32;
+
+        if e_diff.abs() >= 20 {
+            return false;
+        }
 
         if e_diff == 0 {
             return self.mantissa == other.mantissa;
@@ -235,6 +287,24 @@ impl fmt::Display for Number {
     }
 }
 
+// Compare two non-zero decimals, `a * 10^a_e` and `b * 10^b_e`. The number
+// of digits before the decimal point decides, unless it's the same, then the
+// mantissas are compared after padding them to the same number of digits.
+fn cmp_magnitude(a: u64, a_e: i16, b: u64, b_e: i16) -> Ordering {
+    let digits = |num: u64| num.to_string().len() as i32;
+    let (a_digits, b_digits) = (digits(a), digits(b));
+
+    match (a_digits + a_e as i32).cmp(&(b_digits + b_e as i32)) {
+        Ordering::Equal => {
+            // u64 has at most 20 digits, and 10^20 fits in u128 just fine
+            let pad = |num: u64, digits: i32| num as u128 * 10u128.pow((20 - digits) as u32);
+
+            pad(a, a_digits).cmp(&pad(b, b_digits))
+        },
+        ordering => ordering,
+    }
+}
+
 fn exponentiate_f64(n: f64, e: i16) -> f64 {
     static CACHE_POWERS: [f64; 23] = [
           1.0,    1e1,    1e2,    1e3,    1e4,    1e5,    1e6,    1e7,
@@ -292,17 +362,87 @@ impl From<Number> for f64 {
     fn from(num: Number) -> f64 {
         if num.is_nan() { return f64::NAN; }
 
-        let mut n = num.mantissa as f64;
-        let mut e = num.exponent;
+        // Anything that can't be converted exactly with a single rounding is
+        // left to the standard library to get right, so that any `f64`
+        // survives a round trip to JSON and back.
+        let f = exact_f64(num.mantissa, num.exponent).unwrap_or_else(|| {
+            // Mantissa takes at most 20 digits, exponent at most 6 characters
+            let mut buf = [0u8; 32];
+            let len = {
+                let mut cursor = &mut buf[..];
+                write!(cursor, "{}e{}", num.mantissa, num.exponent).expect("Can't fail");
+                32 - cursor.len()
+            };
+
+            str::from_utf8(&buf[..len]).ok()
+                .and_then(|literal| literal.parse().ok())
+                .expect("Can't fail")
+        });
 
-        if e < -308 {
-            n = exponentiate_f64(n, e + 308);
-            e = -308;
+        if num.is_sign_positive() { f } else { -f }
+    }
+}
+
+// Correctly rounded `mantissa * 10^exponent`, for the cases that can be
+// computed with integer arithmetic alone.
+fn exact_f64(mantissa: u64, exponent: i16) -> Option<f64> {
+    // When both the mantissa and the power of ten are exactly representable
+    // as `f64`, a single multiplication or division is correctly rounded.
+    if mantissa < 1 << 53 && (-22..=22).contains(&exponent) {
+        return Some(exponentiate_f64(mantissa as f64, exponent));
+    }
+
+    if exponent >= 0 {
+        // Casting an integer to `f64` is correctly rounded
+        let power = 10u128.checked_pow(exponent as u32)?;
+
+        return (mantissa as u128).checked_mul(power).map(|n| n as f64);
+    }
+
+    if exponent < -22 {
+        return None;
+    }
+
+    // A mantissa past 2^53 is rounded when cast, so the quotient is only
+    // close. It's nudged by a bit at a time until it's the nearest `f64`,
+    // comparing exactly against the midpoints to its neighbours.
+    let power = 10u128.pow(-exponent as u32);
+    let mut f = mantissa as f64 / exponentiate_f64(1.0, -exponent);
+
+    for _ in 0..4 {
+        let bits = f.to_bits();
+        let biased = (bits >> 52) as i32;
+
+        // Subnormals and powers of two, with a closer neighbour below,
+        // aren't worth the trouble
+        if biased == 0 || bits & ((1 << 52) - 1) == 0 {
+            return None;
         }
 
-        let f = exponentiate_f64(n, e);
-        if num.is_sign_positive() { f } else { -f }
+        // `f` is `m * 2^e`, the midpoints are `(2m ± 1) * 2^(e - 1)`
+        let m = (bits & ((1 << 52) - 1)) as u128 | 1 << 52;
+        let shift = 1 - (biased - 1075);
+
+        if !(0..=64).contains(&shift) {
+            return None;
+        }
+
+        // Scaled by `2^(1 - e) * 10^-exponent`, so everything is an integer
+        let value = (mantissa as u128) << shift;
+        let above = (2 * m + 1) * power;
+        let below = (2 * m - 1) * power;
+        let odd = m & 1 == 1;
+
+        if value > above || value == above && odd {
+            f = f64::from_bits(bits + 1);
+        } else if value < below || value == below && odd {
+            f = f64::from_bits(bits - 1);
+        } else {
+            return Some(f);
+        }
     }
+
+    None
 }
 
 impl From<Number> for f32 {
@@ -440,7 +580,8 @@ macro_rules! impl_signed {
                     Number {
                         category: NEGATIVE,
                         exponent: 0,
-                        mantissa: -num as u64,
+                        // Negating in `i64` with wrapping handles `MIN` correctly
+                        mantissa: (num as i64).wrapping_neg() as u64,
                     }
                 } else {
                     Number {
@@ -8,6 +8,21 @@ use crate::value::JsonValue;
 const KEY_BUF_LEN: usize = 32;
 static NULL: JsonValue = JsonValue::Null;
 
+// Bytes an allocator may keep aside for its own bookkeeping with every
+// allocation, on top of rounding the requested size up.
+const ALLOCATION_HEADER: usize = 16;
+
+// Upper bound on the memory taken up by a heap allocation of `size` bytes.
+// Common allocators round requests up to size classes that never exceed the
+// next power of two, and may put a header in front of it.
+pub(crate) fn allocation_size(size: usize) -> usize {
+    if size == 0 {
+        0
+    } else {
+        size.next_power_of_two() + ALLOCATION_HEADER
+    }
+}
+
 // FNV-1a implementation
 //
 // While the `Object` is implemented as a binary tree, not a hash table, the
@@ -40,7 +55,7 @@ static NULL: JsonValue = JsonValue::Null;
 // 15043799550796757486  <-- 3rd
 // ```
 #[inline]
-fn hash_key(key: &[u8]) -> u64 {
+pub(crate) fn hash_key(key: &[u8]) -> u64 {
     let mut hash: u64 = 0xcbf29ce484222325;
     for byte in key {
         hash ^= *byte as u64;
@@ -224,6 +239,15 @@ impl Node {
 /// A binary tree implementation of a string -> `JsonValue` map. You normally don't
 /// have to interact with instances of `Object`, much more likely you will be
 /// using the `JsonValue::Object` variant, which wraps around this struct.
+///
+/// Entries are stored in the order they were inserted in, which for parsed
+/// objects is the order of keys in the source, and both iteration and
+/// serialization follow it. Lookups go through the tree instead.
+///
+/// Keys of up to 32 bytes are stored inline in the entry itself, so the same
+/// short key repeated across many objects, such as in an array of records,
+/// doesn't need an allocation of its own. Only longer keys are allocated
+/// separately on the heap.
 #[derive(Debug)]
 pub struct Object {
     store: Vec<Node>
@@ -494,6 +518,33 @@ impl Object {
         self.store.clear();
     }
 
+    // Bytes allocated for the nodes, long keys and the heap data of values,
+    // used by `JsonValue::estimated_size`.
+    pub(crate) fn heap_size(&self) -> usize {
+        let mut size = allocation_size(self.store.capacity() * mem::size_of::<Node>());
+
+        for node in self.store.iter() {
+            if node.key.len > KEY_BUF_LEN {
+                size += allocation_size(node.key.len);
+            }
+            size += node.value.heap_size();
+        }
+
+        size
+    }
+
+    /// Shrink the capacity of the `Object` as much as possible. This does
+    /// not descend into the values, use `JsonValue::shrink_to_fit` for that.
+    pub fn shrink_to_fit(&mut self) {
+        self.store.shrink_to_fit();
+
+        // Shrinking can move the `Node`s to a new allocation, leaving
+        // dangling pointers for short keys.
+        for node in self.store.iter_mut() {
+            node.key.fix_ptr();
+        }
+    }
+
     #[inline(always)]
     pub fn iter(&self) -> Iter {
         Iter {
@@ -553,6 +604,62 @@ impl<K: AsRef<str>, V: Into<JsonValue>> FromIterator<(K, V)> for Object {
     }
 }
 
+/// Chainable builder for `JsonValue::Object`, for when keys are computed or
+/// only present under some condition, which the `object!` macro can't do.
+///
+/// ```
+/// # #[macro_use] extern crate json;
+/// # use json::object::ObjectBuilder;
+/// # fn main() {
+/// let verbose = false;
+///
+/// let data = ObjectBuilder::new()
+///     .set("name", "foo")
+///     .set(format!("id_{}", 1), 42)
+///     .set_if(verbose, "debug", true)
+///     .build();
+///
+/// assert_eq!(data, object!{ name: "foo", id_1: 42 });
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ObjectBuilder {
+    object: Object,
+}
+
+impl ObjectBuilder {
+    pub fn new() -> Self {
+        ObjectBuilder {
+            object: Object::new(),
+        }
+    }
+
+    /// Set the value behind `key`, overriding any value set previously.
+    pub fn set<K: AsRef<str>, V: Into<JsonValue>>(mut self, key: K, value: V) -> Self {
+        self.object.insert(key.as_ref(), value.into());
+        self
+    }
+
+    /// Same as `set`, but only if `condition` is true.
+    pub fn set_if<K: AsRef<str>, V: Into<JsonValue>>(self, condition: bool, key: K, value: V) -> Self {
+        if condition {
+            self.set(key, value)
+        } else {
+            self
+        }
+    }
+
+    pub fn build(self) -> JsonValue {
+        JsonValue::Object(self.object)
+    }
+}
+
+impl Default for ObjectBuilder {
+    fn default() -> Self {
+        ObjectBuilder::new()
+    }
+}
+
 // Because keys can inserted in different order, the safe way to
 // compare `Object`s is to iterate over one and check if the other
 // has all the same keys.
@@ -17,26 +17,33 @@
 // This makes for some ugly code, but it is faster. Hopefully in the future
 // with MIR support the compiler will get smarter about this.
 
-use std::{str, slice};
+use std::{str, slice, mem};
+use std::borrow::Cow;
 use std::char::decode_utf16;
 use std::convert::TryFrom;
 use crate::object::Object;
-use crate::number::Number;
-use crate::{JsonValue, Error, Result};
+use crate::number::{Number, NAN, INFINITY};
+use crate::short::{self, Short};
+use crate::{JsonValue, BorrowedValue, Error, Result};
+use crate::value::pointer::{ tokens, unescape_token, parse_index };
 
 // This is not actual max precision, but a threshold at which number parsing
 // kicks into checked math.
 const MAX_PRECISION: u64 = 576460752303423500;
 
 
+// Largest magnitude up to which every integer is exactly representable as f64.
+const MAX_EXACT_INTEGER: u64 = 1 << 53;
+
+
 // How many nested Objects/Arrays are allowed to be parsed
 const DEPTH_LIMIT: usize = 512;
 
 
-// The `Parser` struct keeps track of indexing over our buffer. All niceness
+// The `ParserState` struct keeps track of indexing over our buffer. All niceness
 // has been abandoned in favor of raw pointer magic. Does that make you feel
 // dirty? _Good._
-struct Parser<'a> {
+struct ParserState<'a> {
     // Helper buffer for parsing strings that can't be just memcopied from
     // the original source (escaped characters)
     buffer: Vec<u8>,
@@ -52,6 +59,31 @@ struct Parser<'a> {
 
     // Length of the source
     length: usize,
+
+    // Reject integer literals that can't be represented exactly as f64
+    exact_integers: bool,
+
+    // Duplicate object keys seen so far, only collected when set
+    duplicates: Option<Vec<String>>,
+
+    // Keep number literals verbatim, see `JsonValue::as_raw_number`
+    preserve_numbers: bool,
+
+    // Accept `NaN`, `Infinity` and `-Infinity` as numbers
+    lenient_numbers: bool,
+
+    // Accept a leading `+`, `0x` hexadecimal integers, and fractions with
+    // no digits before or after the dot, such as `1.` and `.5`
+    relaxed_numbers: bool,
+
+    // Maximum number of keys in a single object, this and the node limit
+    // are only checked by `parse_value_inner::<true>`
+    max_keys: usize,
+
+    // Maximum number of values in the whole document, along with the count
+    // of values parsed so far
+    max_nodes: usize,
+    nodes: usize,
 }
 
 
@@ -159,6 +191,13 @@ macro_rules! expect {
 
 // Look up table that marks which characters are allowed in their raw
 // form in a string.
+//
+// Note that all bytes >= 0x80 are allowed. Since the source is a `&str` it
+// is already guaranteed to be valid UTF-8, so multibyte characters can be
+// treated as opaque bytes that get copied over as they are. The scanner
+// never decodes characters on the happy path (only when reporting an error),
+// which makes ASCII and non-ASCII input take the exact same, fast route,
+// and an up front ASCII check would be pure overhead.
 const QU: bool = false;  // double quote       0x22
 const BS: bool = false;  // backslash          0x5C
 const CT: bool = false;  // control character  0x00 ..= 0x1F
@@ -225,6 +264,7 @@ macro_rules! expect_string {
 // Expect a number. Of some kind.
 macro_rules! expect_number {
     ($parser:ident, $first:ident) => ({
+        let start = $parser.index - 1;
         let mut num = ($first - b'0') as u64;
 
         let result: Number;
@@ -257,6 +297,10 @@ macro_rules! expect_number {
             }
         }
 
+        if $parser.exact_integers {
+            $parser.check_exact_integer(start)?;
+        }
+
         result
     })
 }
@@ -364,14 +408,26 @@ macro_rules! expect_fraction {
     })
 }
 
-impl<'a> Parser<'a> {
+impl<'a> ParserState<'a> {
     pub fn new(source: &'a str) -> Self {
-        Parser {
-            buffer: Vec::with_capacity(30),
+        ParserState::with_buffer(source, Vec::with_capacity(30))
+    }
+
+    fn with_buffer(source: &'a str, buffer: Vec<u8>) -> Self {
+        ParserState {
+            buffer,
             source: source,
             byte_ptr: source.as_ptr(),
             index: 0,
             length: source.len(),
+            exact_integers: false,
+            duplicates: None,
+            preserve_numbers: false,
+            lenient_numbers: false,
+            relaxed_numbers: false,
+            max_keys: usize::MAX,
+            max_nodes: usize::MAX,
+            nodes: 0,
         }
     }
 
@@ -404,28 +460,159 @@ impl<'a> Parser<'a> {
     // So we got an unexpected character, now what? Well, figure out where
     // it is, and throw an error!
     fn unexpected_character<T: Sized>(&mut self) -> Result<T> {
-        let at = self.index - 1;
+        Err(unexpected_character_at(self.source, self.index - 1))
+    }
 
-        let ch = self.source[at..]
-                     .chars()
-                     .next()
-                     .expect("Must have a character");
+    // Read a number same as `parse_value` would, but keep the literal as it
+    // is in the source instead of its value.
+    fn read_raw_number(&mut self, ch: u8) -> Result<JsonValue> {
+        let start = self.index - 1;
 
-        let (lineno, col) = self.source[..at]
-                                .lines()
-                                .enumerate()
-                                .last()
-                                .unwrap_or((0, ""));
+        self.read_number_literal(ch)?;
 
-        let colno = col.chars().count();
+        raw_number_at(self.source, start, self.index)
+    }
 
-        Err(Error::UnexpectedCharacter {
-            ch: ch,
-            line: lineno + 1,
-            column: colno + 1,
+    // Validate a number literal and get its value, for `read_raw_number`.
+    fn read_number_literal(&mut self, ch: u8) -> Result<Number> {
+        Ok(match ch {
+            b'-' => {
+                let ch = expect_byte!(self);
+                - match ch {
+                    b'0' => allow_number_extensions!(self),
+                    b'1' ..= b'9' => expect_number!(self, ch),
+                    _    => return self.unexpected_character()
+                }
+            },
+            b'0' => allow_number_extensions!(self),
+            b'1' ..= b'9' => expect_number!(self, ch),
+            _ => return self.unexpected_character(),
         })
     }
 
+    // Read a number with `relaxed_numbers` set. This doesn't go through the
+    // fast path macros, instead the whole literal is scanned first, and then
+    // handed over to `u64::from_str_radix` for hexadecimals, or to `f64`
+    // parsing for everything else.
+    fn read_relaxed_number(&mut self, ch: u8) -> Result<Number> {
+        let start = self.index - 1;
+        let negative = ch == b'-';
+
+        if ch != b'-' && ch != b'+' {
+            self.index = start;
+        }
+
+        let digits_start = self.index;
+
+        if self.peek_byte() == Some(b'0') && matches!(self.source.as_bytes().get(self.index + 1), Some(b'x') | Some(b'X')) {
+            self.index += 2;
+
+            let hex_start = self.index;
+
+            while let Some(b'0' ..= b'9') | Some(b'a' ..= b'f') | Some(b'A' ..= b'F') = self.peek_byte() {
+                self.bump();
+            }
+
+            if self.index == hex_start {
+                return self.unexpected_next();
+            }
+
+            let num = u64::from_str_radix(&self.source[hex_start..self.index], 16)
+                .map_err(|_| Error::IntegerOverflow(self.source[start..self.index].into()))?;
+
+            return Ok(if negative { -Number::from(num) } else { Number::from(num) });
+        }
+
+        let integer = self.skip_digits();
+
+        // Leading zeros could be mistaken for C-style octals, so those
+        // are rejected same as in strict mode.
+        if integer > 1 && self.source.as_bytes()[digits_start] == b'0' {
+            self.index = digits_start + 2;
+            return self.unexpected_character();
+        }
+
+        let mut digits = integer;
+
+        if self.peek_byte() == Some(b'.') {
+            self.bump();
+            digits += self.skip_digits();
+        }
+
+        if digits == 0 {
+            return self.unexpected_next();
+        }
+
+        if let Some(b'e') | Some(b'E') = self.peek_byte() {
+            self.bump();
+
+            if let Some(b'+') | Some(b'-') = self.peek_byte() {
+                self.bump();
+            }
+
+            if self.skip_digits() == 0 {
+                return self.unexpected_next();
+            }
+        }
+
+        let number: f64 = self.source[start..self.index].parse().expect("Literal was validated");
+
+        Ok(number.into())
+    }
+
+    fn peek_byte(&mut self) -> Option<u8> {
+        if self.is_eof() {
+            None
+        } else {
+            Some(self.read_byte())
+        }
+    }
+
+    // Skip over decimal digits, returning how many were skipped.
+    fn skip_digits(&mut self) -> usize {
+        let start = self.index;
+
+        while let Some(b'0' ..= b'9') = self.peek_byte() {
+            self.bump();
+        }
+
+        self.index - start
+    }
+
+    // Error out on the next byte, which wasn't expected.
+    fn unexpected_next<T>(&mut self) -> Result<T> {
+        if self.is_eof() {
+            return Err(Error::UnexpectedEndOfJson);
+        }
+
+        self.bump();
+        self.unexpected_character()
+    }
+
+    // Called after reading a number starting at `start` when parsing with
+    // `exact_integers` set. Fractions and `e` notation are let through.
+    #[cold]
+    fn check_exact_integer(&self, start: usize) -> Result<()> {
+        let literal = &self.source[start..self.index];
+
+        if !literal.bytes().all(|ch| ch.is_ascii_digit()) {
+            return Ok(());
+        }
+
+        match literal.parse::<u64>() {
+            Ok(num) if num <= MAX_EXACT_INTEGER => Ok(()),
+            _ => {
+                // Include the sign of negative numbers in the error
+                let start = match start.checked_sub(1) {
+                    Some(sign) if self.source.as_bytes()[sign] == b'-' => sign,
+                    _ => start,
+                };
+
+                Err(Error::IntegerOverflow(self.source[start..self.index].into()))
+            }
+        }
+    }
+
     // Boring
     fn read_hexdec_digit(&mut self) -> Result<u16> {
         let ch = expect_byte!(self);
@@ -547,6 +734,74 @@ impl<'a> Parser<'a> {
         })
     }
 
+    // Same as the `expect_string!` macro, except that the happy path
+    // produces a slice of the source with the full `'a` lifetime, so that
+    // it can be kept around by a `BorrowedValue`. Escaped strings still go
+    // through the buffer and have to be copied out of it.
+    fn read_cow_string(&mut self) -> Result<Cow<'a, str>> {
+        let source = self.source;
+        let start = self.index;
+
+        loop {
+            let ch = expect_byte!(self);
+            if ALLOWED[ch as usize] {
+                continue;
+            }
+            if ch == b'"' {
+                return Ok(Cow::Borrowed(&source[start .. self.index - 1]));
+            }
+            if ch == b'\\' {
+                return Ok(Cow::Owned(self.read_complex_string(start)?.to_owned()));
+            }
+
+            return self.unexpected_character();
+        }
+    }
+
+    // Skip over a single value without building it, leaving the parser right
+    // after it. Strings are checked same as in `parse`, everything else only
+    // needs to have balanced brackets.
+    fn skip_value(&mut self) -> Result<()> {
+        let mut depth = 0usize;
+        let mut ch = expect_byte_ignore_whitespace!(self);
+
+        if let b',' | b':' | b'}' | b']' = ch {
+            return self.unexpected_character();
+        }
+
+        loop {
+            match ch {
+                b'"' => {
+                    expect_string!(self);
+
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                },
+                b'{' | b'[' => depth += 1,
+                b',' | b'}' | b']' if depth == 0 => {
+                    // End of a primitive, leave the delimiter for the caller
+                    self.index -= 1;
+                    return Ok(());
+                },
+                b'}' | b']' => {
+                    depth -= 1;
+
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                },
+                _ => {}
+            }
+
+            if depth == 0 && self.is_eof() {
+                return Ok(());
+            }
+
+            ch = expect_byte!(self);
+        }
+    }
+
     // Big numbers! If the `expect_number!` reaches a point where the decimal
     // mantissa could have overflown the size of u64, it will switch to this
     // control path instead. This method will pick up where the macro started,
@@ -623,12 +878,37 @@ impl<'a> Parser<'a> {
         Ok(unsafe { Number::from_parts_unchecked(true, num, big_e.saturating_add(e * sign)) })
     }
 
-    // Parse away!
-    fn parse(&mut self) -> Result<JsonValue> {
-        let mut stack = Vec::with_capacity(3);
+    // Parse a complete document, nothing but whitespace may follow the value.
+    fn parse(&mut self, stack: &mut Vec<StackBlock>) -> Result<JsonValue> {
+        let value = self.parse_value(stack)?;
+
+        expect_eof!(self);
+
+        Ok(value)
+    }
+
+    // Parse away! This reads a single value and stops right after it,
+    // leaving the index pointing at whatever follows.
+    #[inline]
+    fn parse_value(&mut self, stack: &mut Vec<StackBlock>) -> Result<JsonValue> {
+        self.parse_value_inner::<false>(stack)
+    }
+
+    // Same as `parse_value`, with `LIMITS` enforcing `max_keys` and
+    // `max_nodes`. Being a const parameter, the checks are compiled out of
+    // the regular parser entirely.
+    fn parse_value_inner<const LIMITS: bool>(&mut self, stack: &mut Vec<StackBlock>) -> Result<JsonValue> {
         let mut ch = expect_byte_ignore_whitespace!(self);
 
         'parsing: loop {
+            if LIMITS {
+                self.nodes += 1;
+
+                if self.nodes > self.max_nodes {
+                    return Err(Error::ExceededNodeLimit(self.max_nodes));
+                }
+            }
+
             let mut value = match ch {
                 b'[' => {
                     ch = expect_byte_ignore_whitespace!(self);
@@ -661,6 +941,10 @@ impl<'a> Parser<'a> {
                         let index = object.insert_index(expect_string!(self), JsonValue::Null);
                         expect!(self, b':');
 
+                        if LIMITS && self.max_keys == 0 {
+                            return Err(Error::ExceededKeyLimit(0));
+                        }
+
                         stack.push(StackBlock(JsonValue::Object(object), index));
 
                         ch = expect_byte_ignore_whitespace!(self);
@@ -671,6 +955,10 @@ impl<'a> Parser<'a> {
                     JsonValue::Object(Object::new())
                 },
                 b'"' => expect_string!(self).into(),
+                b'0' ..= b'9' | b'-' if self.preserve_numbers => self.read_raw_number(ch)?,
+                b'0' ..= b'9' | b'-' | b'+' | b'.' if self.relaxed_numbers => {
+                    JsonValue::Number(self.read_relaxed_number(ch)?)
+                },
                 b'0' => JsonValue::Number(allow_number_extensions!(self)),
                 b'1' ..= b'9' => {
                     JsonValue::Number(expect_number!(self, ch))
@@ -680,6 +968,10 @@ impl<'a> Parser<'a> {
                     JsonValue::Number(- match ch {
                         b'0' => allow_number_extensions!(self),
                         b'1' ..= b'9' => expect_number!(self, ch),
+                        b'I' if self.lenient_numbers => {
+                            expect_sequence!(self, b'n', b'f', b'i', b'n', b'i', b't', b'y');
+                            INFINITY
+                        },
                         _    => return self.unexpected_character()
                     })
                 }
@@ -695,16 +987,20 @@ impl<'a> Parser<'a> {
                     expect_sequence!(self, b'u', b'l', b'l');
                     JsonValue::Null
                 },
+                b'N' if self.lenient_numbers => {
+                    expect_sequence!(self, b'a', b'N');
+                    JsonValue::Number(NAN)
+                },
+                b'I' if self.lenient_numbers => {
+                    expect_sequence!(self, b'n', b'f', b'i', b'n', b'i', b't', b'y');
+                    JsonValue::Number(INFINITY)
+                },
                 _    => return self.unexpected_character()
             };
 
             'popping: loop {
                 match stack.last_mut() {
-                    None => {
-                        expect_eof!(self);
-
-                        return Ok(value);
-                    },
+                    None => return Ok(value),
 
                     Some(&mut StackBlock(JsonValue::Array(ref mut array), _)) => {
                         array.push(value);
@@ -730,9 +1026,21 @@ impl<'a> Parser<'a> {
                         match ch {
                             b',' => {
                                 expect!(self, b'"');
-                                *index = object.insert_index(expect_string!(self), JsonValue::Null);
+                                let key = expect_string!(self);
+
+                                if let Some(ref mut duplicates) = self.duplicates {
+                                    if object.get(key).is_some() {
+                                        duplicates.push(key.into());
+                                    }
+                                }
+
+                                *index = object.insert_index(key, JsonValue::Null);
                                 expect!(self, b':');
 
+                                if LIMITS && object.len() > self.max_keys {
+                                    return Err(Error::ExceededKeyLimit(self.max_keys));
+                                }
+
                                 ch = expect_byte_ignore_whitespace!(self);
 
                                 continue 'parsing;
@@ -752,16 +1060,757 @@ impl<'a> Parser<'a> {
             }
         }
     }
+
+    // Exactly like `parse_value`, except producing a `BorrowedValue`.
+    // The stack keeps the already parsed entries of objects along with
+    // the key for the value that is currently being parsed.
+    fn parse_borrowed_value(&mut self) -> Result<BorrowedValue<'a>> {
+        let mut stack = Vec::with_capacity(3);
+        let mut ch = expect_byte_ignore_whitespace!(self);
+
+        'parsing: loop {
+            let mut value = match ch {
+                b'[' => {
+                    ch = expect_byte_ignore_whitespace!(self);
+
+                    if ch != b']' {
+                        if stack.len() == DEPTH_LIMIT {
+                            return Err(Error::ExceededDepthLimit);
+                        }
+
+                        stack.push(BorrowedBlock::Array(Vec::with_capacity(2)));
+                        continue 'parsing;
+                    }
+
+                    BorrowedValue::Array(Vec::new())
+                },
+                b'{' => {
+                    ch = expect_byte_ignore_whitespace!(self);
+
+                    if ch != b'}' {
+                        if stack.len() == DEPTH_LIMIT {
+                            return Err(Error::ExceededDepthLimit);
+                        }
+
+                        if ch != b'"' {
+                            return self.unexpected_character()
+                        }
+
+                        let key = self.read_cow_string()?;
+                        expect!(self, b':');
+
+                        stack.push(BorrowedBlock::Object(Vec::with_capacity(3), key));
+
+                        ch = expect_byte_ignore_whitespace!(self);
+
+                        continue 'parsing;
+                    }
+
+                    BorrowedValue::Object(Vec::new())
+                },
+                b'"' => BorrowedValue::String(self.read_cow_string()?),
+                b'0' => BorrowedValue::Number(allow_number_extensions!(self)),
+                b'1' ..= b'9' => {
+                    BorrowedValue::Number(expect_number!(self, ch))
+                },
+                b'-' => {
+                    let ch = expect_byte!(self);
+                    BorrowedValue::Number(- match ch {
+                        b'0' => allow_number_extensions!(self),
+                        b'1' ..= b'9' => expect_number!(self, ch),
+                        _    => return self.unexpected_character()
+                    })
+                }
+                b't' => {
+                    expect_sequence!(self, b'r', b'u', b'e');
+                    BorrowedValue::Boolean(true)
+                },
+                b'f' => {
+                    expect_sequence!(self, b'a', b'l', b's', b'e');
+                    BorrowedValue::Boolean(false)
+                },
+                b'n' => {
+                    expect_sequence!(self, b'u', b'l', b'l');
+                    BorrowedValue::Null
+                },
+                _    => return self.unexpected_character()
+            };
+
+            loop {
+                match stack.last_mut() {
+                    None => return Ok(value),
+
+                    Some(&mut BorrowedBlock::Array(ref mut array)) => {
+                        array.push(value);
+
+                        ch = expect_byte_ignore_whitespace!(self);
+
+                        match ch {
+                            b',' => {
+                                ch = expect_byte_ignore_whitespace!(self);
+
+                                continue 'parsing;
+                            },
+                            b']' => {},
+                            _    => return self.unexpected_character()
+                        }
+                    },
+
+                    Some(&mut BorrowedBlock::Object(ref mut object, ref mut key)) => {
+                        object.push((mem::replace(key, Cow::Borrowed("")), value));
+
+                        ch = expect_byte_ignore_whitespace!(self);
+
+                        match ch {
+                            b',' => {
+                                expect!(self, b'"');
+                                *key = self.read_cow_string()?;
+                                expect!(self, b':');
+
+                                ch = expect_byte_ignore_whitespace!(self);
+
+                                continue 'parsing;
+                            },
+                            b'}' => {},
+                            _    => return self.unexpected_character()
+                        }
+                    },
+                }
+
+                value = match stack.pop() {
+                    Some(BorrowedBlock::Array(array))       => BorrowedValue::Array(array),
+                    Some(BorrowedBlock::Object(object, _))  => BorrowedValue::Object(object),
+                    None                                    => unreachable!(),
+                }
+            }
+        }
+    }
 }
 
 struct StackBlock(JsonValue, usize);
 
+/// A parser that can be used to parse any number of documents, retaining
+/// the scratch buffers it uses (for unescaping strings and for keeping track
+/// of nested arrays and objects) between them. When parsing a lot of small
+/// documents in a loop, this avoids allocating those buffers anew for every
+/// single one of them, as `json::parse` has to.
+///
+/// ```
+/// # #[macro_use] extern crate json;
+/// # fn main() {
+/// let mut parser = json::Parser::new();
+///
+/// for id in 0..3 {
+///     let source = format!(r#"{{"id":{},"tags":["a","b"]}}"#, id);
+///     let data = parser.parse(&source).unwrap();
+///
+///     assert_eq!(data, object!{ id: id, tags: ["a", "b"] });
+/// }
+/// # }
+/// ```
+pub struct Parser {
+    buffer: Vec<u8>,
+    stack: Vec<StackBlock>,
+}
+
+impl Parser {
+    /// Create a new `Parser`. Buffers are allocated lazily, and will grow
+    /// to fit the largest document parsed so far.
+    pub fn new() -> Self {
+        Parser {
+            buffer: Vec::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Parse a complete JSON document, same as `json::parse`.
+    pub fn parse(&mut self, source: &str) -> Result<JsonValue> {
+        let buffer = mem::take(&mut self.buffer);
+        let mut state = ParserState::with_buffer(source, buffer);
+
+        let result = state.parse(&mut self.stack);
+
+        // After an error the stack can still hold partially parsed values,
+        // and the buffer always holds the contents of escaped strings.
+        self.stack.clear();
+        self.buffer = state.buffer;
+        self.buffer.clear();
+
+        result
+    }
+}
+
+impl Default for Parser {
+    fn default() -> Self {
+        Parser::new()
+    }
+}
+
+enum BorrowedBlock<'a> {
+    Array(Vec<BorrowedValue<'a>>),
+    Object(Vec<(Cow<'a, str>, BorrowedValue<'a>)>, Cow<'a, str>),
+}
+
+// Produce an `Error::UnexpectedCharacter` for the character starting at byte
+// `at`, with its line and column in the source.
+pub(crate) fn unexpected_character_at(source: &str, at: usize) -> Error {
+    let ch = source[at..]
+                 .chars()
+                 .next()
+                 .expect("Must have a character");
+
+    let (lineno, col) = source[..at]
+                            .lines()
+                            .enumerate()
+                            .last()
+                            .unwrap_or((0, ""));
+
+    let colno = col.chars().count();
+
+    Error::UnexpectedCharacter {
+        ch: ch,
+        line: lineno + 1,
+        column: colno + 1,
+    }
+}
+
+// Keep the already validated number literal at `source[start..end]` as it
+// is. It's stored as a `Short`, which limits it to `short::MAX_LEN` bytes,
+// anything longer errors rather than being silently rounded.
+fn raw_number_at(source: &str, start: usize, end: usize) -> Result<JsonValue> {
+    let literal = &source[start..end];
+
+    if literal.len() > short::MAX_LEN {
+        return Err(Error::InvalidSyntax {
+            line: source[..start].matches('\n').count() + 1,
+            expected: format!("number literal of at most {} bytes, got {}", short::MAX_LEN, literal),
+        });
+    }
+
+    Ok(JsonValue::Short(unsafe { Short::raw_number(literal) }))
+}
+
 // All that hard work, and in the end it's just a single function in the API.
 #[inline]
 pub fn parse(source: &str) -> Result<JsonValue> {
-    Parser::new(source).parse()
+    ParserState::new(source).parse(&mut Vec::with_capacity(3))
+}
+
+/// Parse a JSON document same as `json::parse`, except that integer literals
+/// with a magnitude above 2^53, which can't be represented exactly as `f64`,
+/// produce an `Error::IntegerOverflow` carrying the literal text instead of
+/// being silently rounded. Numbers with a fraction or an exponent are
+/// accepted as usual.
+///
+/// ```
+/// # use json::Error;
+/// let data = json::parse_exact_integers("[9007199254740992, 1.5e300]").unwrap();
+///
+/// assert_eq!(data[0], 9007199254740992u64);
+/// assert_eq!(
+///     json::parse_exact_integers("9007199254740993"),
+///     Err(Error::IntegerOverflow("9007199254740993".into()))
+/// );
+/// ```
+pub fn parse_exact_integers(source: &str) -> Result<JsonValue> {
+    let mut parser = ParserState::new(source);
+    parser.exact_integers = true;
+
+    parser.parse(&mut Vec::with_capacity(3))
+}
+
+/// Parse a JSON document same as `json::parse`, guarding against documents
+/// that are excessively large in ways other than their length. Errors with
+/// `Error::ExceededKeyLimit` if any single object has more than `max_keys`
+/// distinct keys, and with `Error::ExceededNodeLimit` if the document has
+/// more than `max_nodes` values in total, counting objects and arrays
+/// themselves, as well as all their members. Both errors carry the limit
+/// that has been exceeded.
+///
+/// ```
+/// # use json::Error;
+/// assert!(json::parse_with_limits(r#"{"a":[1,2]}"#, 1, 4).is_ok());
+/// assert_eq!(json::parse_with_limits(r#"{"a":1,"b":2}"#, 1, 4), Err(Error::ExceededKeyLimit(1)));
+/// assert_eq!(json::parse_with_limits("[1,2,3,4]", 1, 4), Err(Error::ExceededNodeLimit(4)));
+/// ```
+pub fn parse_with_limits(source: &str, max_keys: usize, max_nodes: usize) -> Result<JsonValue> {
+    let mut parser = ParserState::new(source);
+    parser.max_keys = max_keys;
+    parser.max_nodes = max_nodes;
+
+    let value = parser.parse_value_inner::<true>(&mut Vec::with_capacity(3))?;
+
+    expect_eof!(parser);
+
+    Ok(value)
+}
+
+/// Parse a JSON document same as `json::parse`, except that all numbers are
+/// kept as the number literal exactly as it was written. Serializing the
+/// result will reproduce every number byte for byte, including big integers
+/// and trailing zeroes, which makes it possible to relay documents without
+/// altering them.
+///
+/// Raw numbers still work with all the numeric accessors, such as `as_f64`,
+/// and compare equal to other numbers of the same value. The literal itself
+/// is available through `JsonValue::as_raw_number`. Literals are stored
+/// inline, without a heap allocation, which limits them to 30 bytes, enough
+/// for 30 digits. Longer literals error with `Error::InvalidSyntax` rather
+/// than being rounded.
+///
+/// ```
+/// let source = r#"{"price":1.000,"id":123456789012345678901234567890}"#;
+/// let data = json::parse_preserve_numbers(source).unwrap();
+///
+/// assert_eq!(data["price"].as_f64(), Some(1.0));
+/// assert_eq!(data["price"].as_raw_number(), Some("1.000"));
+/// assert_eq!(data["price"], 1);
+/// assert_eq!(data.dump(), source);
+/// ```
+pub fn parse_preserve_numbers(source: &str) -> Result<JsonValue> {
+    let mut parser = ParserState::new(source);
+    parser.preserve_numbers = true;
+
+    parser.parse(&mut Vec::with_capacity(3))
+}
+
+// Validate a single number literal and get its value, for
+// `JsonValue::raw_number` and the numeric accessors of raw numbers.
+pub(crate) fn parse_raw_number(source: &str) -> Result<Number> {
+    let mut parser = ParserState::new(source);
+
+    let ch = expect_byte!(parser);
+    let number = parser.read_number_literal(ch)?;
+
+    if !parser.is_eof() {
+        parser.bump();
+        return parser.unexpected_character();
+    }
+
+    Ok(number)
+}
+
+// Validate a single number literal for `JsonValue::raw_number`.
+pub(crate) fn raw_number(source: &str) -> Result<JsonValue> {
+    parse_raw_number(source)?;
+
+    raw_number_at(source, 0, source.len())
 }
 
+/// Parse a JSON document same as `json::parse`, except that the non-standard
+/// `NaN`, `Infinity` and `-Infinity` tokens are accepted as numbers, as
+/// produced by Python's `json` module and JavaScript's `String()`.
+///
+/// `Number` has no representation for infinity, so `Infinity` is stored as
+/// the largest number it can hold, `1e32767`, which converts to infinite
+/// `f64` and `f32`, and is written out as such when serialized. `NaN` is
+/// stored as NaN, which serializes as `null`.
+///
+/// ```
+/// let data = json::parse_lenient_numbers("[NaN, Infinity, -Infinity]").unwrap();
+///
+/// assert!(data[0].as_f64().unwrap().is_nan());
+/// assert_eq!(data[1].as_f64(), Some(std::f64::INFINITY));
+/// assert_eq!(data[2].as_f64(), Some(std::f64::NEG_INFINITY));
+/// assert!(json::parse("[NaN]").is_err());
+/// ```
+pub fn parse_lenient_numbers(source: &str) -> Result<JsonValue> {
+    let mut parser = ParserState::new(source);
+    parser.lenient_numbers = true;
+
+    parser.parse(&mut Vec::with_capacity(3))
+}
+
+/// Parse a JSON document same as `json::parse`, except that numbers may be
+/// written in a few non-standard ways, as emitted by some C-style producers:
+/// with a leading `+`, as `0x` hexadecimal integers, or as fractions missing
+/// the digits on either side of the dot, such as `1.` or `.5`.
+///
+/// Hexadecimal integers have to fit in 64 bits, all other numbers are parsed
+/// as `f64`, so large integers lose the precision they'd keep with
+/// `json::parse`. Leading zeros are still rejected.
+///
+/// ```
+/// let data = json::parse_relaxed_numbers("[+5, 0x1F, -0x10, 1., .5]").unwrap();
+///
+/// assert_eq!(data, json::parse("[5, 31, -16, 1, 0.5]").unwrap());
+/// assert!(json::parse("[+5]").is_err());
+/// ```
+pub fn parse_relaxed_numbers(source: &str) -> Result<JsonValue> {
+    let mut parser = ParserState::new(source);
+    parser.relaxed_numbers = true;
+
+    parser.parse(&mut Vec::with_capacity(3))
+}
+
+/// Parse a JSON document same as `json::parse`, invoking `f` with every
+/// object key that appears more than once within the same object. The last
+/// occurrence of a key still wins, so the resulting value is unaffected.
+///
+/// Keys are passed to `f` in order of appearance, once parsing has finished.
+/// This also happens if the document turns out to be invalid, in which case
+/// `f` will see all duplicates up to the point of the error.
+///
+/// ```
+/// # #[macro_use] extern crate json;
+/// # fn main() {
+/// let mut duplicates = Vec::new();
+///
+/// let data = json::parse_with_dedup_callback(r#"{"a":1,"b":2,"a":3}"#, |key| {
+///     duplicates.push(key.to_string());
+/// }).unwrap();
+///
+/// assert_eq!(data, object!{ a: 3, b: 2 });
+/// assert_eq!(duplicates, ["a"]);
+/// # }
+/// ```
+pub fn parse_with_dedup_callback<F>(source: &str, mut f: F) -> Result<JsonValue>
+where F: FnMut(&str) {
+    let mut parser = ParserState::new(source);
+    parser.duplicates = Some(Vec::new());
+
+    let result = parser.parse(&mut Vec::with_capacity(3));
+
+    for key in parser.duplicates.iter().flatten() {
+        f(key);
+    }
+
+    result
+}
+
+// Walk the value bottom-up for `parse_with_reviver`.
+fn revive<F>(key: &str, mut value: JsonValue, reviver: &mut F) -> Option<JsonValue>
+where F: FnMut(&str, JsonValue) -> Option<JsonValue> {
+    match value {
+        JsonValue::Object(ref mut object) => {
+            let mut revived = Object::with_capacity(object.len());
+
+            for (key, value) in object.iter_mut() {
+                if let Some(value) = revive(key, value.take(), reviver) {
+                    revived.insert(key, value);
+                }
+            }
+
+            *object = revived;
+        },
+        JsonValue::Array(ref mut vec) => {
+            let members = mem::replace(vec, Vec::with_capacity(vec.len()));
+
+            for (index, member) in members.into_iter().enumerate() {
+                if let Some(member) = revive(&index.to_string(), member, reviver) {
+                    vec.push(member);
+                }
+            }
+        },
+        _ => {},
+    }
+
+    reviver(key, value)
+}
+
+/// Parse a JSON document same as `json::parse`, and then transform it with
+/// `reviver`, like `JSON.parse` does in JavaScript. The `reviver` is called
+/// with every key and value, bottom-up, so that members of an object or an
+/// array are revived before the object or array itself is. Members of arrays
+/// are passed with their index as the key, and the root value is passed with
+/// an empty key.
+///
+/// The value returned by `reviver` takes the place of the original one,
+/// returning `None` drops the object entry or array member altogether, which
+/// shifts the indexes of the following members. Dropping the root value
+/// leaves `Null`.
+///
+/// ```
+/// # #[macro_use] extern crate json;
+/// # fn main() {
+/// let data = json::parse_with_reviver(r#"{"id":"42","tags":["7","x"]}"#, |_, value| {
+///     match value.as_str().and_then(|s| s.parse::<u32>().ok()) {
+///         Some(number) => Some(number.into()),
+///         None         => Some(value),
+///     }
+/// }).unwrap();
+///
+/// assert_eq!(data, object!{ id: 42, tags: [7, "x"] });
+/// # }
+/// ```
+pub fn parse_with_reviver<F>(source: &str, mut reviver: F) -> Result<JsonValue>
+where F: FnMut(&str, JsonValue) -> Option<JsonValue> {
+    let value = parse(source)?;
+
+    Ok(revive("", value, &mut reviver).unwrap_or(JsonValue::Null))
+}
+
+/// Parse a JSON document into a `BorrowedValue`, which borrows all strings
+/// and object keys that contain no escape sequences straight from `source`.
+/// See `BorrowedValue` for details.
+pub fn parse_borrowed(source: &str) -> Result<BorrowedValue<'_>> {
+    let mut parser = ParserState::new(source);
+    let value = parser.parse_borrowed_value()?;
+
+    expect_eof!(parser);
+
+    Ok(value)
+}
+
+/// Parse a top level JSON array, invoking `f` with every member as soon as
+/// it has been parsed. Members are dropped after the callback returns, so
+/// memory use is bounded by the size of the largest member, rather than the
+/// whole array. Any error returned from the callback stops the parsing and
+/// is passed through. Errors if the top level value is not an array.
+///
+/// ```
+/// let mut sum = 0.0;
+///
+/// json::parse_array_streaming("[1, 2, 3.5]", |member| {
+///     sum += member.as_f64().unwrap_or(0.0);
+///     Ok(())
+/// }).unwrap();
+///
+/// assert_eq!(sum, 6.5);
+/// ```
+pub fn parse_array_streaming<F>(source: &str, mut f: F) -> Result<()>
+where F: FnMut(JsonValue) -> Result<()> {
+    let mut parser = ParserState::new(source);
+    let mut stack = Vec::with_capacity(3);
+
+    if expect_byte_ignore_whitespace!(parser) != b'[' {
+        return Err(Error::wrong_type("Array"));
+    }
+
+    if expect_byte_ignore_whitespace!(parser) != b']' {
+        // Step back so that `parse_value` can read the byte again.
+        parser.index -= 1;
+
+        loop {
+            f(parser.parse_value(&mut stack)?)?;
+
+            match expect_byte_ignore_whitespace!(parser) {
+                b',' => {},
+                b']' => break,
+                _    => return parser.unexpected_character(),
+            }
+        }
+    }
+
+    expect_eof!(parser);
+
+    Ok(())
+}
+
+/// Parse a top level JSON object, invoking `f` with every key and value as
+/// soon as they have been parsed. Same as `parse_array_streaming`, values
+/// are dropped after the callback returns, and errors from the callback stop
+/// the parsing. Duplicate keys are passed to the callback every time they
+/// appear. Errors if the top level value is not an object.
+///
+/// ```
+/// let mut keys = Vec::new();
+///
+/// json::parse_object_streaming(r#"{"a": 1, "b": [2, 3]}"#, |key, _| {
+///     keys.push(key.to_string());
+///     Ok(())
+/// }).unwrap();
+///
+/// assert_eq!(keys, ["a", "b"]);
+/// ```
+pub fn parse_object_streaming<F>(source: &str, mut f: F) -> Result<()>
+where F: FnMut(&str, JsonValue) -> Result<()> {
+    let mut parser = ParserState::new(source);
+    let mut stack = Vec::with_capacity(3);
+    let mut key = String::new();
+
+    if expect_byte_ignore_whitespace!(parser) != b'{' {
+        return Err(Error::wrong_type("Object"));
+    }
+
+    if expect_byte_ignore_whitespace!(parser) != b'}' {
+        // Step back so that every entry starts the same way.
+        parser.index -= 1;
+
+        loop {
+            if expect_byte_ignore_whitespace!(parser) != b'"' {
+                return parser.unexpected_character();
+            }
+
+            // The key is copied out, so that the parser is free to go on
+            key.clear();
+            key.push_str(expect_string!(parser));
+
+            expect!(parser, b':');
+
+            f(&key, parser.parse_value(&mut stack)?)?;
+
+            match expect_byte_ignore_whitespace!(parser) {
+                b',' => {},
+                b'}' => break,
+                _    => return parser.unexpected_character(),
+            }
+        }
+    }
+
+    expect_eof!(parser);
+
+    Ok(())
+}
+
+/// Decode a quoted JSON string literal, such as `"foo\nbar"`, into a Rust
+/// `String`, resolving all escape sequences including `\u` surrogate pairs.
+/// This is the reverse of `json::escape_string`.
+///
+/// ```
+/// assert_eq!(json::unescape_string(r#""tab\t\u00e9""#).unwrap(), "tab\té");
+/// ```
+pub fn unescape_string(source: &str) -> Result<String> {
+    let mut parser = ParserState::new(source);
+
+    expect!(parser, b'"');
+
+    let string = expect_string!(parser).to_owned();
+
+    expect_eof!(parser);
+
+    Ok(string)
+}
+
+/// Parse a single JSON value from the beginning of `source`, returning it
+/// along with the remainder of the slice that follows it. Unlike `parse`,
+/// anything is allowed after the value, which makes it possible to read
+/// JSON messages that are framed in a larger stream.
+///
+/// ```
+/// # #[macro_use] extern crate json;
+/// # fn main() {
+/// let (value, rest) = json::parse_partial(r#"{"a":1} {"b":2}"#).unwrap();
+///
+/// assert_eq!(value, object!{ a: 1 });
+/// assert_eq!(rest, r#" {"b":2}"#);
+/// # }
+/// ```
+pub fn parse_partial(source: &str) -> Result<(JsonValue, &str)> {
+    let mut parser = ParserState::new(source);
+    let value = parser.parse_value(&mut Vec::with_capacity(3))?;
+
+    Ok((value, &source[parser.index..]))
+}
+
+
+/// Parse only the single value behind a JSON Pointer, such as `/data/0/id`,
+/// out of a larger document. Everything leading up to the target is skimmed
+/// over without building any values, and everything after it isn't looked at
+/// at all, which makes this a lot cheaper than `parse` followed by
+/// `JsonValue::pointer` when only a small part of a big document is needed.
+///
+/// The flip side is that the skipped parts are not fully validated, so an
+/// invalid document can still produce a value. Should an object contain the
+/// same key more than once, the first occurrence is used, unlike in `parse`
+/// where the last one wins.
+///
+/// Errors with `Error::UndefinedField` carrying the pointer if it doesn't
+/// resolve, and with `Error::InvalidArgument` if it's malformed.
+///
+/// ```
+/// # #[macro_use] extern crate json;
+/// # fn main() {
+/// let source = r#"{"meta": {"huge": [1, 2, 3]}, "data": [{"id": 7, "tags": ["a"]}]}"#;
+///
+/// assert_eq!(json::extract_pointer(source, "/data/0/tags").unwrap(), array!["a"]);
+/// assert!(json::extract_pointer(source, "/data/1").is_err());
+/// # }
+/// ```
+pub fn extract_pointer(source: &str, pointer: &str) -> Result<JsonValue> {
+    let tokens = match tokens(pointer) {
+        Some(tokens) => tokens,
+        None         => return Err(Error::invalid_argument(&format!("Expected JSON Pointer, got {:?}", pointer))),
+    };
+
+    let mut parser = ParserState::new(source);
+
+    for token in tokens {
+        let token = unescape_token(token);
+
+        match expect_byte_ignore_whitespace!(parser) {
+            b'{' => {
+                if expect_byte_ignore_whitespace!(parser) == b'}' {
+                    return Err(Error::undefined(pointer));
+                }
+
+                // Step back so that every entry starts the same way.
+                parser.index -= 1;
+
+                loop {
+                    if expect_byte_ignore_whitespace!(parser) != b'"' {
+                        return parser.unexpected_character();
+                    }
+
+                    let found = expect_string!(parser) == token;
+
+                    expect!(parser, b':');
+
+                    if found {
+                        break;
+                    }
+
+                    parser.skip_value()?;
+
+                    match expect_byte_ignore_whitespace!(parser) {
+                        b',' => {},
+                        b'}' => return Err(Error::undefined(pointer)),
+                        _    => return parser.unexpected_character(),
+                    }
+                }
+            },
+            b'[' => {
+                let index = match parse_index(&token) {
+                    Some(index) => index,
+                    None        => return Err(Error::undefined(pointer)),
+                };
+
+                if expect_byte_ignore_whitespace!(parser) == b']' {
+                    return Err(Error::undefined(pointer));
+                }
+
+                parser.index -= 1;
+
+                for _ in 0..index {
+                    parser.skip_value()?;
+
+                    match expect_byte_ignore_whitespace!(parser) {
+                        b',' => {},
+                        b']' => return Err(Error::undefined(pointer)),
+                        _    => return parser.unexpected_character(),
+                    }
+                }
+            },
+            _ => return Err(Error::undefined(pointer)),
+        }
+    }
+
+    parser.parse_value(&mut Vec::with_capacity(3))
+}
+
+/// Parse a JSON document same as `json::parse`, except that anything other
+/// than whitespace following the top level value produces an
+/// `Error::TrailingData` carrying the byte offset at which the trailing data
+/// starts, rather than a generic `Error::UnexpectedCharacter`. This makes it
+/// possible to tell concatenated messages apart from malformed ones.
+///
+/// ```
+/// # use json::Error;
+/// assert_eq!(json::parse_reject_trailing("true false"), Err(Error::TrailingData { offset: 5 }));
+/// assert!(json::parse_reject_trailing("true \n").is_ok());
+/// ```
+pub fn parse_reject_trailing(source: &str) -> Result<JsonValue> {
+    let (value, rest) = parse_partial(source)?;
+
+    // Same set of whitespace bytes as `expect_eof!`
+    let trailing = rest.trim_start_matches(|ch| matches!(ch, '\t' ..= '\r' | ' '));
+
+    if trailing.is_empty() {
+        Ok(value)
+    } else {
+        Err(Error::TrailingData { offset: source.len() - trailing.len() })
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,353 @@
+// A best-effort parser for tooling, that keeps going after an error. It's a
+// plain recursive descent over objects and arrays, primitive values are read
+// with the strict parser via `parse_partial`. Whenever something goes wrong,
+// the error is recorded, and the parser skips ahead to the next `,` or
+// closing bracket on the same level, leaving `Null` in place of the value.
+
+use crate::object::Object;
+use crate::{ JsonValue, Error, parse_partial };
+
+// How many nested Objects/Arrays are allowed to be parsed, same as strict
+const DEPTH_LIMIT: usize = 512;
+
+struct RecoveringParser<'a> {
+    source: &'a str,
+    bytes: &'a [u8],
+    index: usize,
+    depth: usize,
+    errors: Vec<Error>,
+    // Line and column of the byte at `scanned`, errors are reported in
+    // source order, so these only ever have to move forward.
+    scanned: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> RecoveringParser<'a> {
+    fn unexpected_character(&mut self, at: usize) {
+        if at < self.scanned {
+            self.scanned = 0;
+            self.line = 1;
+            self.column = 1;
+        }
+
+        for &byte in &self.bytes[self.scanned..at] {
+            if byte == b'\n' {
+                self.line += 1;
+                self.column = 1;
+            } else if byte & 0xC0 != 0x80 {
+                // Count characters, not UTF-8 continuation bytes
+                self.column += 1;
+            }
+        }
+
+        self.scanned = at;
+
+        let ch = self.source[at..]
+                     .chars()
+                     .next()
+                     .expect("Must have a character");
+
+        self.errors.push(Error::UnexpectedCharacter {
+            ch,
+            line: self.line,
+            column: self.column,
+        });
+    }
+
+    // Every unclosed object or array would run into the end, but it only
+    // needs to be reported once.
+    fn end_of_json(&mut self) {
+        if self.errors.last() != Some(&Error::UnexpectedEndOfJson) {
+            self.errors.push(Error::UnexpectedEndOfJson);
+        }
+    }
+
+    // Skip whitespace and return the next byte without consuming it.
+    fn peek(&mut self) -> Option<u8> {
+        while let Some(&ch) = self.bytes.get(self.index) {
+            match ch {
+                9 ..= 13 | 32 => self.index += 1,
+                _             => return Some(ch),
+            }
+        }
+
+        None
+    }
+
+    // Skip to the next `,`, `]` or `}` that isn't nested in another object,
+    // array or string, without consuming it.
+    fn skip_to_delimiter(&mut self) {
+        let mut depth = 0;
+
+        while let Some(&ch) = self.bytes.get(self.index) {
+            match ch {
+                b',' | b']' | b'}' if depth == 0 => return,
+                b'[' | b'{' => depth += 1,
+                b']' | b'}' => depth -= 1,
+                b'"' => {
+                    self.index += 1;
+
+                    while let Some(&ch) = self.bytes.get(self.index) {
+                        match ch {
+                            b'"'  => break,
+                            b'\\' => self.index += 1,
+                            _     => {},
+                        }
+
+                        self.index += 1;
+                    }
+                },
+                _ => {},
+            }
+
+            self.index += 1;
+        }
+    }
+
+    fn parse_value(&mut self) -> JsonValue {
+        match self.peek() {
+            None       => {
+                self.end_of_json();
+                JsonValue::Null
+            },
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => self.parse_array(),
+            Some(_)    => {
+                let start = self.index;
+
+                match parse_partial(&self.source[start..]) {
+                    Ok((value, rest)) => {
+                        self.index = self.source.len() - rest.len();
+                        value
+                    },
+                    Err(Error::UnexpectedCharacter { column, .. }) => {
+                        // Primitive values never span multiple lines, so only
+                        // the column has to be moved to the right position.
+                        let at = self.source[start..]
+                                     .char_indices()
+                                     .nth(column - 1)
+                                     .map(|(offset, _)| start + offset)
+                                     .unwrap_or(start);
+
+                        self.unexpected_character(at);
+                        self.skip_to_delimiter();
+                        JsonValue::Null
+                    },
+                    Err(error) => {
+                        self.errors.push(error);
+                        self.skip_to_delimiter();
+                        JsonValue::Null
+                    },
+                }
+            }
+        }
+    }
+
+    fn enter(&mut self) -> bool {
+        if self.depth == DEPTH_LIMIT {
+            self.errors.push(Error::ExceededDepthLimit);
+            self.index += 1;
+            self.skip_to_delimiter();
+
+            return false;
+        }
+
+        self.depth += 1;
+        self.index += 1;
+
+        true
+    }
+
+    // Called after a member of an object or an array, returns `false` once
+    // the container is closed, or can't be continued.
+    fn expect_separator(&mut self, closing: u8) -> bool {
+        loop {
+            match self.peek() {
+                None => {
+                    self.end_of_json();
+                    return false;
+                },
+                Some(b',') => {
+                    self.index += 1;
+
+                    // Trailing commas are not allowed
+                    if self.peek() == Some(closing) {
+                        let at = self.index;
+
+                        self.unexpected_character(at);
+                        self.index += 1;
+
+                        return false;
+                    }
+
+                    return true;
+                },
+                Some(ch) if ch == closing => {
+                    self.index += 1;
+                    return false;
+                },
+                Some(_) => {
+                    let at = self.index;
+
+                    self.unexpected_character(at);
+                    self.index += 1;
+                    self.skip_to_delimiter();
+                },
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> JsonValue {
+        if !self.enter() {
+            return JsonValue::Null;
+        }
+
+        let mut object = Object::new();
+
+        if self.peek() == Some(b'}') {
+            self.index += 1;
+        } else {
+            loop {
+                let key = match self.peek() {
+                    Some(b'"') => match self.parse_value() {
+                        JsonValue::Null => None,
+                        key             => key.as_str().map(String::from),
+                    },
+                    None => {
+                        self.end_of_json();
+                        break;
+                    },
+                    Some(_) => {
+                        let at = self.index;
+
+                        self.unexpected_character(at);
+                        self.skip_to_delimiter();
+                        None
+                    },
+                };
+
+                if let Some(key) = key {
+                    match self.peek() {
+                        Some(b':') => {
+                            self.index += 1;
+
+                            let value = self.parse_value();
+
+                            object.insert(&key, value);
+                        },
+                        Some(_) => {
+                            let at = self.index;
+
+                            self.unexpected_character(at);
+                            self.skip_to_delimiter();
+                            object.insert(&key, JsonValue::Null);
+                        },
+                        None => {
+                            self.end_of_json();
+                            object.insert(&key, JsonValue::Null);
+                            break;
+                        },
+                    }
+                }
+
+                if !self.expect_separator(b'}') {
+                    break;
+                }
+            }
+        }
+
+        self.depth -= 1;
+
+        JsonValue::Object(object)
+    }
+
+    fn parse_array(&mut self) -> JsonValue {
+        if !self.enter() {
+            return JsonValue::Null;
+        }
+
+        let mut array = Vec::new();
+
+        if self.peek() == Some(b']') {
+            self.index += 1;
+        } else {
+            loop {
+                if self.peek().is_none() {
+                    self.end_of_json();
+                    break;
+                }
+
+                array.push(self.parse_value());
+
+                if !self.expect_separator(b']') {
+                    break;
+                }
+            }
+        }
+
+        self.depth -= 1;
+
+        JsonValue::Array(array)
+    }
+}
+
+/// Parse a JSON document on a best-effort basis, collecting every error
+/// encountered instead of stopping at the first one, such as for editors
+/// that highlight all problems in a file at once. Values that fail to parse
+/// are replaced by `Null`, and the parser continues with the next member of
+/// the enclosing object or array. Unclosed objects and arrays are closed at
+/// the end of the source.
+///
+/// The returned value is `None` only if no value could be read at all. On
+/// valid JSON the result is the same as for `json::parse`, with no errors.
+///
+/// ```
+/// # #[macro_use] extern crate json;
+/// # fn main() {
+/// let (value, errors) = json::parse_recover("[1, tru, 3, ]");
+///
+/// assert_eq!(value, Some(array![1, null, 3]));
+/// assert_eq!(errors.len(), 2);
+/// # }
+/// ```
+pub fn parse_recover(source: &str) -> (Option<JsonValue>, Vec<Error>) {
+    let mut parser = RecoveringParser {
+        source,
+        bytes: source.as_bytes(),
+        index: 0,
+        depth: 0,
+        errors: Vec::new(),
+        scanned: 0,
+        line: 1,
+        column: 1,
+    };
+
+    if parser.peek().is_none() {
+        return (None, vec![Error::UnexpectedEndOfJson]);
+    }
+
+    let start = parser.index;
+    let value = parser.parse_value();
+
+    // A successfully parsed `null` can't produce any errors, so this means
+    // nothing was read, the top level value failed outright.
+    let value = match value {
+        JsonValue::Null if !parser.errors.is_empty() => None,
+        value => Some(value),
+    };
+
+    // A stray `,`, `]` or `}` is reported by `parse_value` without being
+    // consumed, it must not be reported again as trailing data.
+    if value.is_none() && parser.index == start {
+        parser.index += 1;
+    }
+
+    if parser.peek().is_some() {
+        let at = parser.index;
+
+        parser.unexpected_character(at);
+    }
+
+    (value, parser.errors)
+}
@@ -3,6 +3,11 @@ use std::ops::Deref;
 
 pub const MAX_LEN: usize = 30;
 
+// The highest bit of `len` marks a number literal kept verbatim by
+// `json::parse_preserve_numbers`, rather than a string. Since `MAX_LEN` is
+// well below it, it never collides with the length itself.
+const RAW_NUMBER: u8 = 0x80;
+
 #[derive(Clone, Copy)]
 pub struct Short {
     len: u8,
@@ -32,12 +37,29 @@ impl Short {
         short
     }
 
+    /// Creates a `Short` holding a number literal rather than a string. Same
+    /// as with `from_slice`, the literal must not be longer than `MAX_LEN`.
+    #[inline]
+    pub(crate) unsafe fn raw_number(literal: &str) -> Self {
+        let mut short = Short::from_slice(literal);
+
+        short.len |= RAW_NUMBER;
+
+        short
+    }
+
+    /// Whether this `Short` holds a number literal rather than a string.
+    #[inline]
+    pub(crate) fn is_raw_number(&self) -> bool {
+        self.len & RAW_NUMBER != 0
+    }
+
     /// Cheaply obtain a `&str` slice out of the `Short`.
     #[inline]
     pub fn as_str(&self) -> &str {
         unsafe {
             str::from_utf8_unchecked(
-                slice::from_raw_parts(self.value.as_ptr(), self.len as usize)
+                slice::from_raw_parts(self.value.as_ptr(), (self.len & !RAW_NUMBER) as usize)
             )
         }
     }
@@ -52,6 +74,10 @@ impl PartialEq for Short {
 
 impl fmt::Debug for Short {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_raw_number() {
+            return f.write_str(self.as_str());
+        }
+
         fmt::Debug::fmt(self.as_str(), f)
     }
 }
@@ -0,0 +1,133 @@
+// This is a private module that contains `FrozenValue`, an immutable,
+// cheaply cloneable handle to a `JsonValue`.
+
+use std::fmt;
+use std::ops::Index;
+use std::sync::Arc;
+
+use crate::value::JsonValue;
+
+/// An immutable handle to a `JsonValue`, produced by `JsonValue::freeze`.
+///
+/// Cloning a `FrozenValue` only bumps a reference count, and since the value
+/// behind it can never change, it's `Send + Sync` and can be shared between
+/// threads without any locking.
+///
+/// ```
+/// # #[macro_use] extern crate json;
+/// # fn main() {
+/// let config = object!{ server: { port: 8080 } }.freeze();
+/// let shared = config.clone();
+///
+/// let port = std::thread::spawn(move || shared["server"]["port"].as_u16())
+///     .join()
+///     .unwrap();
+///
+/// assert_eq!(port, Some(8080));
+/// assert_eq!(config.pointer("/server/port"), Some(&json::from(8080)));
+/// # }
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct FrozenValue(Arc<JsonValue>);
+
+impl FrozenValue {
+    /// Get a reference to the whole frozen value.
+    #[inline]
+    pub fn as_value(&self) -> &JsonValue {
+        &self.0
+    }
+
+    /// Works on `JsonValue::Object` - same as `JsonValue::try_get`.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        self.0.try_get(key)
+    }
+
+    /// Works on `JsonValue::Array` - same as `JsonValue::try_at`.
+    #[inline]
+    pub fn at(&self, index: usize) -> Option<&JsonValue> {
+        self.0.try_at(index)
+    }
+
+    /// Look up a value by a JSON Pointer - same as `JsonValue::pointer`.
+    #[inline]
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonValue> {
+        self.0.pointer(pointer)
+    }
+
+    /// Get a mutable copy of the frozen value. This clones the whole value
+    /// unless this is the last handle to it.
+    pub fn thaw(self) -> JsonValue {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone())
+    }
+}
+
+impl JsonValue {
+    /// Turn this value into an immutable `FrozenValue` that can be cheaply
+    /// cloned and shared between threads.
+    pub fn freeze(self) -> FrozenValue {
+        FrozenValue(Arc::new(self))
+    }
+}
+
+impl From<JsonValue> for FrozenValue {
+    fn from(value: JsonValue) -> FrozenValue {
+        value.freeze()
+    }
+}
+
+impl AsRef<JsonValue> for FrozenValue {
+    fn as_ref(&self) -> &JsonValue {
+        &self.0
+    }
+}
+
+impl PartialEq<JsonValue> for FrozenValue {
+    fn eq(&self, other: &JsonValue) -> bool {
+        *self.0 == *other
+    }
+}
+
+impl fmt::Debug for FrozenValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for FrozenValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+impl Index<usize> for FrozenValue {
+    type Output = JsonValue;
+
+    fn index(&self, index: usize) -> &JsonValue {
+        &self.0[index]
+    }
+}
+
+impl Index<&str> for FrozenValue {
+    type Output = JsonValue;
+
+    fn index(&self, index: &str) -> &JsonValue {
+        &self.0[index]
+    }
+}
+
+impl Index<String> for FrozenValue {
+    type Output = JsonValue;
+
+    fn index(&self, index: String) -> &JsonValue {
+        &self.0[index]
+    }
+}
+
+impl Index<&String> for FrozenValue {
+    type Output = JsonValue;
+
+    fn index(&self, index: &String) -> &JsonValue {
+        &self.0[index]
+    }
+}
@@ -1,6 +1,7 @@
 // This is a private module that contains `PartialEq` and `From` trait
 // implementations for `JsonValue`.
 
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 
 use crate::short::{self, Short};
@@ -39,6 +40,32 @@ macro_rules! implement_eq {
     }
 }
 
+// Numbers also compare equal to raw numbers of the same value
+macro_rules! implement_eq_num {
+    ($from:ty) => {
+        impl PartialEq<$from> for JsonValue {
+            fn eq(&self, other: &$from) -> bool {
+                match *self {
+                    JsonValue::Number(ref value) => value == other,
+                    _ => self.as_number().map(|value| value == *other).unwrap_or(false)
+                }
+            }
+        }
+
+        impl<'a> PartialEq<$from> for &'a JsonValue {
+            fn eq(&self, other: &$from) -> bool {
+                **self == *other
+            }
+        }
+
+        impl PartialEq<JsonValue> for $from {
+            fn eq(&self, other: &JsonValue) -> bool {
+                *other == *self
+            }
+        }
+    }
+}
+
 macro_rules! implement {
     ($to:ident, $from:ty as num) => {
         impl From<$from> for JsonValue {
@@ -47,7 +74,7 @@ macro_rules! implement {
             }
         }
 
-        implement_eq!($to, $from);
+        implement_eq_num!($from);
     };
     ($to:ident, $from:ty) => {
         impl From<$from> for JsonValue {
@@ -70,6 +97,15 @@ impl<'a> From<&'a str> for JsonValue {
     }
 }
 
+impl<'a> From<Cow<'a, str>> for JsonValue {
+    fn from(val: Cow<'a, str>) -> JsonValue {
+        match val {
+            Cow::Borrowed(val) => val.into(),
+            Cow::Owned(val)    => val.into(),
+        }
+    }
+}
+
 impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
     fn from(val: Option<T>) -> JsonValue {
         match val {
@@ -106,6 +142,7 @@ impl<K: AsRef<str>, V: Into<JsonValue>> From<BTreeMap<K, V>> for JsonValue {
 impl<'a> PartialEq<&'a str> for JsonValue {
     fn eq(&self, other: &&str) -> bool {
         match *self {
+            JsonValue::Short(ref value) if value.is_raw_number() => false,
             JsonValue::Short(ref value)  => value == *other,
             JsonValue::String(ref value) => value == *other,
             _ => false
@@ -116,6 +153,7 @@ impl<'a> PartialEq<&'a str> for JsonValue {
 impl<'a> PartialEq<JsonValue> for &'a str {
     fn eq(&self, other: &JsonValue) -> bool {
         match *other {
+            JsonValue::Short(ref value) if value.is_raw_number() => false,
             JsonValue::Short(ref value)  => value == *self,
             JsonValue::String(ref value) => value == *self,
             _ => false
@@ -126,6 +164,7 @@ impl<'a> PartialEq<JsonValue> for &'a str {
 impl PartialEq<str> for JsonValue {
     fn eq(&self, other: &str) -> bool {
         match *self {
+            JsonValue::Short(ref value) if value.is_raw_number() => false,
             JsonValue::Short(ref value)  => value == other,
             JsonValue::String(ref value) => value == other,
             _ => false
@@ -136,6 +175,7 @@ impl PartialEq<str> for JsonValue {
 impl<'a> PartialEq<JsonValue> for str {
     fn eq(&self, other: &JsonValue) -> bool {
         match *other {
+            JsonValue::Short(ref value) if value.is_raw_number() => false,
             JsonValue::Short(ref value)  => value == self,
             JsonValue::String(ref value) => value == self,
             _ => false
@@ -156,6 +196,6 @@ implement!(Number, u32 as num);
 implement!(Number, u64 as num);
 implement!(Number, f32 as num);
 implement!(Number, f64 as num);
-implement!(Number, Number);
+implement!(Number, Number as num);
 implement!(Object, Object);
 implement!(Boolean, bool);
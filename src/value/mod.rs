@@ -1,4 +1,5 @@
 use std::ops::{Index, IndexMut, Deref};
+use std::cmp::Ordering;
 use std::convert::TryInto;
 use std::{fmt, mem, usize, u8, u16, u32, u64, isize, i8, i16, i32, i64, f32};
 use std::io::{self, Write};
@@ -6,11 +7,20 @@ use std::io::{self, Write};
 use crate::{Result, Error};
 use crate::short::Short;
 use crate::number::Number;
-use crate::object::Object;
+use crate::object::{Object, allocation_size};
 use crate::iterators::{ Members, MembersMut, Entries, EntriesMut };
 use crate::codegen::{ Generator, PrettyGenerator, DumpGenerator, WriterGenerator, PrettyWriterGenerator };
 
 mod implements;
+mod query;
+pub(crate) mod pointer;
+mod properties;
+mod frozen;
+mod time;
+mod xml;
+mod schema;
+
+pub use self::frozen::FrozenValue;
 
 // These are convenience macros for converting `f64` to the `$unsigned` type.
 // The macros check that the numbers are representable the target type.
@@ -48,6 +58,15 @@ pub enum JsonValue {
 impl PartialEq for JsonValue {
     fn eq(&self, other: &Self) -> bool {
         use self::JsonValue::*;
+
+        // Raw numbers compare by value, both to each other and to numbers
+        if self.as_raw_number().is_some() || other.as_raw_number().is_some() {
+            return match (self.as_number(), other.as_number()) {
+                (Some(a), Some(b)) => a == b,
+                _                  => false,
+            };
+        }
+
         match (self, other) {
             (&Null, &Null) => true,
             (&Short(ref a), &Short(ref b)) => a == b,
@@ -65,6 +84,270 @@ impl PartialEq for JsonValue {
 
 impl Eq for JsonValue {}
 
+impl Ord for JsonValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total_cmp(other)
+    }
+}
+
+impl PartialOrd for JsonValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Order values of the same primitive kind, `None` for anything else.
+fn compare_values(a: &JsonValue, b: &JsonValue) -> Option<Ordering> {
+    if a.is_number() && b.is_number() {
+        return a.as_f64()?.partial_cmp(&b.as_f64()?);
+    }
+
+    match (a.as_str(), b.as_str()) {
+        (Some(a), Some(b)) => return Some(a.cmp(b)),
+        (None, None)       => {},
+        _                  => return None,
+    }
+
+    match (a.as_bool(), b.as_bool()) {
+        (Some(a), Some(b)) => Some(a.cmp(&b)),
+        _                  => None,
+    }
+}
+
+// Turn raw numbers into regular ones, so they write out the same as any
+// other number of the same value, see `content_hash`.
+fn drop_raw_literals(value: &mut JsonValue) {
+    match *value {
+        JsonValue::Short(ref short) if short.is_raw_number() => {
+            *value = JsonValue::Number(value.as_number().expect("Is a number"));
+        },
+        JsonValue::Object(ref mut object) => {
+            for (_, value) in object.iter_mut() {
+                drop_raw_literals(value);
+            }
+        },
+        JsonValue::Array(ref mut vec) => {
+            for member in vec.iter_mut() {
+                drop_raw_literals(member);
+            }
+        },
+        _ => {}
+    }
+}
+
+// Total order over values already known to be of one kind by
+// `compare_values`, anything else compares as equal.
+fn total_compare(a: &JsonValue, b: &JsonValue) -> Ordering {
+    if let (Some(a), Some(b)) = (a.as_f64(), b.as_f64()) {
+        return a.total_cmp(&b);
+    }
+
+    if let (Some(a), Some(b)) = (a.as_str(), b.as_str()) {
+        return a.cmp(b);
+    }
+
+    match (a.as_bool(), b.as_bool()) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _                  => Ordering::Equal,
+    }
+}
+
+// Sort object entries by key, numerically if every key is made of ASCII
+// digits, lexically otherwise.
+fn sort_entries<T>(entries: &mut [(&str, T)]) {
+    let numeric = entries.iter().all(|&(key, _)| {
+        !key.is_empty() && key.bytes().all(|byte| byte.is_ascii_digit())
+    });
+
+    if numeric {
+        // Compare by magnitude first, ignoring any leading zeroes
+        entries.sort_by(|a, b| {
+            let (a_trimmed, b_trimmed) = (a.0.trim_start_matches('0'), b.0.trim_start_matches('0'));
+
+            a_trimmed.len().cmp(&b_trimmed.len())
+                .then_with(|| a_trimmed.cmp(b_trimmed))
+                .then_with(|| a.0.cmp(b.0))
+        });
+    } else {
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+    }
+}
+
+// Write out a value with sorted object keys, see `dump_sorted_keys`.
+fn write_sorted_keys(gen: &mut DumpGenerator, value: &JsonValue) -> io::Result<()> {
+    match *value {
+        JsonValue::Array(ref vec) => {
+            gen.write_char(b'[')?;
+
+            for (index, member) in vec.iter().enumerate() {
+                if index != 0 {
+                    gen.write_char(b',')?;
+                }
+                write_sorted_keys(gen, member)?;
+            }
+
+            gen.write_char(b']')
+        },
+        JsonValue::Object(ref object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+
+            sort_entries(&mut entries);
+
+            gen.write_char(b'{')?;
+
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index != 0 {
+                    gen.write_char(b',')?;
+                }
+                gen.write_string(key)?;
+                gen.write_char(b':')?;
+                write_sorted_keys(gen, value)?;
+            }
+
+            gen.write_char(b'}')
+        },
+        _ => gen.write_json(value),
+    }
+}
+
+// Write out a value with long arrays and strings cut short, see
+// `to_truncated_string`.
+fn write_truncated(gen: &mut DumpGenerator, value: &JsonValue, max_array: usize, max_string: usize) -> io::Result<()> {
+    match *value {
+        JsonValue::Short(ref short) if short.is_raw_number() => gen.write_json(value),
+        JsonValue::Short(_) | JsonValue::String(_) => {
+            let string = value.as_str().expect("Is a string");
+
+            match string.char_indices().nth(max_string) {
+                Some((end, _)) => gen.write_string(&format!("{}…", &string[..end])),
+                None           => gen.write_string(string),
+            }
+        },
+        JsonValue::Array(ref vec) => {
+            gen.write_char(b'[')?;
+
+            for (index, member) in vec.iter().take(max_array).enumerate() {
+                if index != 0 {
+                    gen.write_char(b',')?;
+                }
+                write_truncated(gen, member, max_array, max_string)?;
+            }
+
+            if vec.len() > max_array {
+                if max_array != 0 {
+                    gen.write_char(b',')?;
+                }
+                write!(gen.get_writer(), "…(+{} more)", vec.len() - max_array)?;
+            }
+
+            gen.write_char(b']')
+        },
+        JsonValue::Object(ref object) => {
+            gen.write_char(b'{')?;
+
+            for (index, (key, value)) in object.iter().enumerate() {
+                if index != 0 {
+                    gen.write_char(b',')?;
+                }
+                gen.write_string(key)?;
+                gen.write_char(b':')?;
+                write_truncated(gen, value, max_array, max_string)?;
+            }
+
+            gen.write_char(b'}')
+        },
+        _ => gen.write_json(value),
+    }
+}
+
+// Write out the members of a value as substituted by `replacer`, see
+// `to_string_with_replacer`.
+fn write_replaced<F>(gen: &mut DumpGenerator, value: &JsonValue, replacer: &mut F) -> io::Result<()>
+where F: FnMut(&str, &JsonValue) -> Option<JsonValue> {
+    match *value {
+        JsonValue::Array(ref vec) => {
+            gen.write_char(b'[')?;
+
+            let mut first = true;
+
+            for (index, member) in vec.iter().enumerate() {
+                if let Some(member) = replacer(&index.to_string(), member) {
+                    if !first {
+                        gen.write_char(b',')?;
+                    }
+                    first = false;
+                    write_replaced(gen, &member, replacer)?;
+                }
+            }
+
+            gen.write_char(b']')
+        },
+        JsonValue::Object(ref object) => {
+            gen.write_char(b'{')?;
+
+            let mut first = true;
+
+            for (key, value) in object.iter() {
+                if let Some(value) = replacer(key, value) {
+                    if !first {
+                        gen.write_char(b',')?;
+                    }
+                    first = false;
+                    gen.write_string(key)?;
+                    gen.write_char(b':')?;
+                    write_replaced(gen, &value, replacer)?;
+                }
+            }
+
+            gen.write_char(b'}')
+        },
+        _ => gen.write_json(value),
+    }
+}
+
+// Merge `other` into `target`, see `merge_with`.
+fn merge_values(target: &mut JsonValue, mut other: JsonValue, strategy: ArrayMergeStrategy) {
+    match (target, &mut other) {
+        (&mut JsonValue::Object(ref mut target), &mut JsonValue::Object(ref mut other)) => {
+            for (key, value) in other.iter_mut() {
+                match target.get_mut(key) {
+                    Some(existing) => merge_values(existing, value.take(), strategy),
+                    None           => target.insert(key, value.take()),
+                }
+            }
+        },
+        (&mut JsonValue::Array(ref mut target), &mut JsonValue::Array(ref mut other))
+            if strategy != ArrayMergeStrategy::Replace => {
+            let mut members = other.drain(..);
+
+            if strategy == ArrayMergeStrategy::ByIndex {
+                for existing in target.iter_mut() {
+                    match members.next() {
+                        Some(member) => merge_values(existing, member, strategy),
+                        None         => break,
+                    }
+                }
+            }
+
+            target.extend(members);
+        },
+        (target, _) => *target = other,
+    }
+}
+
+/// How arrays that are present on both sides are combined by
+/// `JsonValue::merge_with`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayMergeStrategy {
+    /// The array being merged in replaces the existing one.
+    Replace,
+    /// Members of the array being merged in are appended to the existing one.
+    Concat,
+    /// Members at the same index are merged recursively, any extra members
+    /// of the array being merged in are appended.
+    ByIndex,
+}
+
 /// Implements formatting
 ///
 /// ```
@@ -106,6 +389,70 @@ impl JsonValue {
         JsonValue::Array(Vec::new())
     }
 
+    /// Create a number that is kept exactly as the given literal, same as
+    /// the numbers produced by `json::parse_preserve_numbers`. Errors if the
+    /// literal is anything but a single JSON number without any surrounding
+    /// whitespace, or if it's longer than 30 bytes.
+    ///
+    /// ```
+    /// # use json::JsonValue;
+    /// let price = JsonValue::raw_number("1.000").unwrap();
+    ///
+    /// assert_eq!(price.dump(), "1.000");
+    /// assert_eq!(price, 1);
+    /// assert!(JsonValue::raw_number("1.").is_err());
+    /// ```
+    pub fn raw_number(literal: &str) -> Result<JsonValue> {
+        crate::parser::raw_number(literal)
+    }
+
+    /// Create a `JsonValue::Array` of `len` nulls, to be filled in by index.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # use json::JsonValue;
+    /// # fn main() {
+    /// let mut data = JsonValue::array_of_len(3);
+    ///
+    /// data[1] = "second".into();
+    ///
+    /// assert_eq!(data, array![null, "second", null]);
+    /// # }
+    /// ```
+    pub fn array_of_len(len: usize) -> JsonValue {
+        JsonValue::Array(vec![JsonValue::Null; len])
+    }
+
+    /// Create a `JsonValue::Object` from a CSV record, taking keys from
+    /// `headers` and values from `fields` at the same position. All values
+    /// are kept as strings, no types are inferred. Should a header appear
+    /// more than once, the field that comes last wins. Errors with
+    /// `Error::InvalidArgument` if there isn't exactly one field for every
+    /// header.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # use json::JsonValue;
+    /// # fn main() {
+    /// let data = JsonValue::from_csv_record(&["id", "name"], &["1", "Jane"]).unwrap();
+    ///
+    /// assert_eq!(data, object!{ id: "1", name: "Jane" });
+    /// # }
+    /// ```
+    pub fn from_csv_record(headers: &[&str], fields: &[&str]) -> Result<JsonValue> {
+        if headers.len() != fields.len() {
+            return Err(Error::invalid_argument(&format!("Record of {} fields, got {}", headers.len(), fields.len())));
+        }
+
+        let mut object = Object::with_capacity(headers.len());
+
+        for (header, field) in headers.iter().zip(fields) {
+            object.insert(header, JsonValue::from(*field));
+        }
+
+        Ok(JsonValue::Object(object))
+    }
+
     /// Prints out the value as JSON string.
     pub fn dump(&self) -> String {
         let mut gen = DumpGenerator::new();
@@ -121,6 +468,258 @@ impl JsonValue {
         gen.consume()
     }
 
+    /// Prints out the value as a canonical JSON string meant purely for
+    /// comparison, such as in golden-file tests. Object keys are sorted, and
+    /// members of every array are sorted by their own stable serialization,
+    /// recursively.
+    ///
+    /// **Note:** this is lossy with respect to array order, the output is not
+    /// a valid reconstruction of the original value and should not be parsed
+    /// back as one.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let a = object!{ ids: [3, 1, 2], name: "foo" };
+    /// let b = object!{ name: "foo", ids: [2, 3, 1] };
+    ///
+    /// assert_eq!(a.to_string_stable(), r#"{"ids":[1,2,3],"name":"foo"}"#);
+    /// assert_eq!(a.to_string_stable(), b.to_string_stable());
+    /// # }
+    /// ```
+    pub fn to_string_stable(&self) -> String {
+        match *self {
+            JsonValue::Array(ref vec) => {
+                let mut members: Vec<String> = vec.iter().map(JsonValue::to_string_stable).collect();
+                members.sort();
+
+                format!("[{}]", members.join(","))
+            },
+            JsonValue::Object(ref object) => {
+                let mut entries: Vec<_> = object.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+
+                let entries: Vec<String> = entries.into_iter().map(|(key, value)| {
+                    format!("{}:{}", crate::escape_string(key), value.to_string_stable())
+                }).collect();
+
+                format!("{{{}}}", entries.join(","))
+            },
+            _ => self.dump()
+        }
+    }
+
+    /// Prints out the value as JSON string, with the keys of every object in
+    /// sorted order, while arrays keep their order. Objects in which every
+    /// key is made of ASCII digits, such as objects used as sparse arrays,
+    /// are sorted numerically so that `"2"` comes before `"10"`, all other
+    /// objects are sorted lexically.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ "10": "c", "2": "b", "1": "a" };
+    ///
+    /// assert_eq!(data.dump_sorted_keys(), r#"{"1":"a","2":"b","10":"c"}"#);
+    /// # }
+    /// ```
+    pub fn dump_sorted_keys(&self) -> String {
+        let mut gen = DumpGenerator::new();
+        write_sorted_keys(&mut gen, self).expect("Can't fail");
+        gen.consume()
+    }
+
+    /// Prints out the value as JSON string that is safe to embed in HTML,
+    /// such as inside of a `<script>` tag. On top of the regular escaping,
+    /// `<`, `>` and `&` are written as `\u003c`, `\u003e` and `\u0026`, so
+    /// that `</script>` or `<!--` can't appear in the output, and the line
+    /// and paragraph separators `U+2028` and `U+2029` are escaped, as they
+    /// are not allowed in JavaScript string literals in older engines.
+    ///
+    /// The output is still valid JSON that parses back into the same value.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ html: "</script>" };
+    ///
+    /// assert_eq!(data.to_html_safe_string(), r#"{"html":"\u003c/script\u003e"}"#);
+    /// # }
+    /// ```
+    pub fn to_html_safe_string(&self) -> String {
+        let dump = self.dump();
+
+        // All of these can only ever be found inside of strings
+        if !dump.contains(&['<', '>', '&', '\u{2028}', '\u{2029}'][..]) {
+            return dump;
+        }
+
+        let mut safe = String::with_capacity(dump.len() + 16);
+
+        for ch in dump.chars() {
+            match ch {
+                '<'        => safe.push_str("\\u003c"),
+                '>'        => safe.push_str("\\u003e"),
+                '&'        => safe.push_str("\\u0026"),
+                '\u{2028}' => safe.push_str("\\u2028"),
+                '\u{2029}' => safe.push_str("\\u2029"),
+                ch         => safe.push(ch),
+            }
+        }
+
+        safe
+    }
+
+    /// Compute a 64-bit hash of the contents of the value, such as for ETags
+    /// or memoization. It's computed over the output of `dump_sorted_keys`
+    /// after `normalize_numbers`, so values that only differ in the order of
+    /// object keys or in how equal numbers are written, like `1.0` and `1`,
+    /// hash the same, and it's stable between runs and platforms. This is not a
+    /// cryptographic hash, and must not be relied on to detect tampering.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let a = object!{ id: 1, tags: ["x", "y"] };
+    /// let b = object!{ tags: ["x", "y"], id: 1 };
+    ///
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    /// # }
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        let mut canonical = self.clone();
+
+        drop_raw_literals(&mut canonical);
+        canonical.normalize_numbers();
+
+        crate::object::hash_key(canonical.dump_sorted_keys().as_bytes())
+    }
+
+    /// Prints out the value as JSON string, passing every key and value
+    /// through `replacer` first, like `JSON.stringify` does in JavaScript.
+    /// The `replacer` is called top-down, starting with the root value and an
+    /// empty key, followed by the members of whatever value it returned.
+    /// Members of arrays are passed with their index as the key.
+    ///
+    /// Returning `Some` writes out the value returned in place of the
+    /// original one, returning `None` omits the object entry or array member
+    /// altogether. Omitting the root value writes out `null`. This mirrors
+    /// `json::parse_with_reviver`.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ user: "jane", password: "hunter2", email: null };
+    ///
+    /// let dump = data.to_string_with_replacer(|key, value| match key {
+    ///     "password"           => Some("***".into()),
+    ///     _ if value.is_null() => None,
+    ///     _                    => Some(value.clone()),
+    /// });
+    ///
+    /// assert_eq!(dump, r#"{"user":"jane","password":"***"}"#);
+    /// # }
+    /// ```
+    pub fn to_string_with_replacer<F>(&self, mut replacer: F) -> String
+    where F: FnMut(&str, &JsonValue) -> Option<JsonValue> {
+        let mut gen = DumpGenerator::new();
+        let root = replacer("", self).unwrap_or(JsonValue::Null);
+
+        write_replaced(&mut gen, &root, &mut replacer).expect("Can't fail");
+        gen.consume()
+    }
+
+    /// Prints out a summary of the value for log lines, serialized compactly
+    /// with arrays of more than `max_array` members and strings of more than
+    /// `max_string` characters cut short. Truncated arrays end with
+    /// `…(+N more)` in place of the `N` members left out, and truncated
+    /// strings end with `…`.
+    ///
+    /// **Note:** the output is not valid JSON once anything is truncated,
+    /// and it should not be parsed back.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ ids: [1, 2, 3, 4, 5], name: "a long name" };
+    ///
+    /// assert_eq!(data.to_truncated_string(2, 6), r#"{"ids":[1,2,…(+3 more)],"name":"a long…"}"#);
+    /// # }
+    /// ```
+    pub fn to_truncated_string(&self, max_array: usize, max_string: usize) -> String {
+        let mut gen = DumpGenerator::new();
+        write_truncated(&mut gen, self, max_array, max_string).expect("Can't fail");
+        gen.consume()
+    }
+
+    /// Render any value as something printable, for log lines and the like.
+    /// This is the same as the `Display` implementation, except for `null`:
+    ///
+    /// - strings are written as they are, without quotes or escaping
+    /// - numbers and booleans are formatted as they would be in JSON
+    /// - `null` becomes an empty string
+    /// - objects and arrays are written out as compact JSON
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// assert_eq!(json::from("foo").to_display_string(), "foo");
+    /// assert_eq!(json::Null.to_display_string(), "");
+    /// assert_eq!(array!["foo", null].to_display_string(), r#"["foo",null]"#);
+    /// # }
+    /// ```
+    pub fn to_display_string(&self) -> String {
+        match *self {
+            JsonValue::Null => String::new(),
+            _               => self.to_string(),
+        }
+    }
+
+    /// Works on `JsonValue::Array` of objects - turn them into rows of a CSV
+    /// table. The header row is the sorted union of keys of all objects, and
+    /// each object becomes a row of its values in the same order, formatted
+    /// as by `to_display_string`. Keys missing from an object are left empty.
+    /// Errors if called on anything but an array, or if any of its members is
+    /// not an object.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = array![{ name: "Jane", age: 30 }, { name: "John" }];
+    ///
+    /// let (headers, rows) = data.to_csv_rows().unwrap();
+    ///
+    /// assert_eq!(headers, ["age", "name"]);
+    /// assert_eq!(rows, [["30", "Jane"], ["", "John"]]);
+    /// # }
+    /// ```
+    pub fn to_csv_rows(&self) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        let records = match *self {
+            JsonValue::Array(ref vec) => vec,
+            _ => return Err(Error::wrong_type("Array")),
+        };
+
+        let mut headers = Vec::new();
+
+        for record in records {
+            match *record {
+                JsonValue::Object(ref object) => {
+                    headers.extend(object.iter().map(|(key, _)| key.to_owned()));
+                },
+                _ => return Err(Error::wrong_type("Array of objects")),
+            }
+        }
+
+        headers.sort();
+        headers.dedup();
+
+        let rows = records.iter().map(|record| {
+            headers.iter().map(|key| record[key.as_str()].to_display_string()).collect()
+        }).collect();
+
+        Ok((headers, rows))
+    }
+
     /// Writes the JSON as byte stream into an implementor of `std::io::Write`.
     ///
     /// This method is deprecated as it will panic on io errors, use `write` instead.
@@ -142,18 +741,32 @@ impl JsonValue {
         gen.write_json(self)
     }
 
+    /// Same as `write_pretty`, but if `trailing_newline` is true the document
+    /// will be followed by a `\n`, as expected by most editors and tools
+    /// when writing files.
+    pub fn write_pretty_to<W: Write>(&self, writer: &mut W, spaces: u16, trailing_newline: bool) -> io::Result<()> {
+        self.write_pretty(writer, spaces)?;
+
+        if trailing_newline {
+            writer.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+
     pub fn is_string(&self) -> bool {
         match *self {
-            JsonValue::Short(_)  => true,
-            JsonValue::String(_) => true,
-            _                    => false,
+            JsonValue::Short(ref value) => !value.is_raw_number(),
+            JsonValue::String(_)        => true,
+            _                           => false,
         }
     }
 
     pub fn is_number(&self) -> bool {
         match *self {
-            JsonValue::Number(_) => true,
-            _                    => false,
+            JsonValue::Number(_)        => true,
+            JsonValue::Short(ref value) => value.is_raw_number(),
+            _                           => false,
         }
     }
 
@@ -196,6 +809,9 @@ impl JsonValue {
     pub fn is_empty(&self) -> bool {
         match *self {
             JsonValue::Null               => true,
+            JsonValue::Short(ref value) if value.is_raw_number() => {
+                self.as_number().map(|number| number.is_empty()).unwrap_or(false)
+            },
             JsonValue::Short(ref value)   => value.is_empty(),
             JsonValue::String(ref value)  => value.is_empty(),
             JsonValue::Number(ref value)  => value.is_empty(),
@@ -207,23 +823,86 @@ impl JsonValue {
 
     pub fn as_str(&self) -> Option<&str> {
         match *self {
+            JsonValue::Short(ref value) if value.is_raw_number() => None,
             JsonValue::Short(ref value)  => Some(value),
             JsonValue::String(ref value) => Some(value),
             _                            => None
         }
     }
 
+    /// Get a string, provided it's one of the `allowed` variants, matched
+    /// exactly. Errors with `Error::WrongType` listing the allowed variants
+    /// if it's not, or if called on anything but a string.
+    ///
+    /// ```
+    /// # use json::JsonValue;
+    /// let allowed = ["active", "inactive"];
+    ///
+    /// assert_eq!(JsonValue::from("active").as_enum(&allowed), Ok("active"));
+    /// assert_eq!(
+    ///     JsonValue::from("unknown").as_enum(&allowed).unwrap_err().to_string(),
+    ///     r#"Wrong type, expected: One of ["active", "inactive"], got "unknown""#
+    /// );
+    /// ```
+    pub fn as_enum(&self, allowed: &[&str]) -> Result<&str> {
+        match self.as_str() {
+            Some(value) if allowed.contains(&value) => Ok(value),
+            Some(value) => Err(Error::wrong_type(&format!("One of {:?}, got {:?}", allowed, value))),
+            None        => Err(Error::wrong_type("String")),
+        }
+    }
+
     pub fn as_number(&self) -> Option<Number> {
         match *self {
             JsonValue::Number(value) => Some(value),
+            JsonValue::Short(ref value) if value.is_raw_number() => {
+                crate::parser::parse_raw_number(value).ok()
+            },
             _                        => None
         }
     }
 
+    /// Get the number literal of a number parsed with
+    /// `json::parse_preserve_numbers`, exactly as it was written in the
+    /// source. Returns `None` for anything else, including regular numbers.
+    ///
+    /// ```
+    /// let data = json::parse_preserve_numbers("[1.50, 2]").unwrap();
+    ///
+    /// assert_eq!(data[0].as_raw_number(), Some("1.50"));
+    /// assert_eq!(data[0].as_f64(), Some(1.5));
+    /// assert_eq!(json::from(1.5).as_raw_number(), None);
+    /// ```
+    pub fn as_raw_number(&self) -> Option<&str> {
+        match *self {
+            JsonValue::Short(ref value) if value.is_raw_number() => Some(value),
+            _ => None
+        }
+    }
+
     pub fn as_f64(&self) -> Option<f64> {
         self.as_number().map(|value| value.into())
     }
 
+    /// Same as `as_f64`, but guaranteed to return a finite number. Errors
+    /// with `Error::NonFiniteNumber` if the number is NaN, such as the result
+    /// of converting an infinite float into a `JsonValue`, or too large to be
+    /// represented as `f64`, and with `Error::WrongType` for non-numbers.
+    ///
+    /// ```
+    /// # use json::JsonValue;
+    /// assert_eq!(JsonValue::from(1.5).as_finite_number(), Ok(1.5));
+    /// assert_eq!(JsonValue::from(std::f64::NAN).as_finite_number(), Err(json::Error::NonFiniteNumber));
+    /// assert!(json::parse("1e400").unwrap().as_finite_number().is_err());
+    /// ```
+    pub fn as_finite_number(&self) -> Result<f64> {
+        match self.as_f64() {
+            Some(number) if number.is_finite() => Ok(number),
+            Some(_) => Err(Error::NonFiniteNumber),
+            None    => Err(Error::wrong_type("Number")),
+        }
+    }
+
     pub fn as_f32(&self) -> Option<f32> {
         self.as_number().map(|value| value.into())
     }
@@ -238,8 +917,43 @@ impl JsonValue {
         self.as_u64().and_then(|value| number_to_unsigned!(u32, value, u64))
     }
 
-    pub fn as_u16(&self) -> Option<u16> {
-        self.as_u64().and_then(|value| number_to_unsigned!(u16, value, u64))
+    /// Range-checked conversion to `u32`. Unlike `as_u32`, any number with an
+    /// integral value is accepted, no matter how it's written, so `1.0` and
+    /// `1e2` work too. Errors with `Error::IntegerOutOfRange` if the number
+    /// has a fraction or doesn't fit, with `Error::NonFiniteNumber` for NaN
+    /// and with `Error::WrongType` for non-numbers.
+    ///
+    /// ```
+    /// # use json::JsonValue;
+    /// assert_eq!(json::parse("1e2").unwrap().as_u32_checked(), Ok(100));
+    /// assert!(JsonValue::from(-1).as_u32_checked().is_err());
+    /// assert!(JsonValue::from(1.5).as_u32_checked().is_err());
+    /// ```
+    pub fn as_u32_checked(&self) -> Result<u32> {
+        self.as_integer_in_range(0.0, u32::MAX as f64).map(|value| value as u32)
+    }
+
+    // The full range of 32 bit integers is exactly representable as `f64`,
+    // so the bounds checks are exact as well.
+    fn as_integer_in_range(&self, min: f64, max: f64) -> Result<f64> {
+        let number = match self.as_f64() {
+            Some(number) => number,
+            None         => return Err(Error::wrong_type("Number")),
+        };
+
+        if !number.is_finite() {
+            return Err(Error::NonFiniteNumber);
+        }
+
+        if number.fract() != 0.0 || number < min || number > max {
+            return Err(Error::IntegerOutOfRange(self.dump()));
+        }
+
+        Ok(number)
+    }
+
+    pub fn as_u16(&self) -> Option<u16> {
+        self.as_u64().and_then(|value| number_to_unsigned!(u16, value, u64))
     }
 
     pub fn as_u8(&self) -> Option<u8> {
@@ -254,10 +968,76 @@ impl JsonValue {
         self.as_number().and_then(|value| value.try_into().ok())
     }
 
+    /// Get an integral number as its exact decimal string, without going
+    /// through `f64`. Numbers keep up to 64 bits of their decimal mantissa
+    /// when parsed, so a large ID such as `9007199254740993` comes out exactly,
+    /// even though `as_f64` would round it to `9007199254740992`. Numbers
+    /// created from an `f64` have already been rounded, and so stay that way,
+    /// same as literals with more digits than fit in a `u64` mantissa.
+    ///
+    /// Any number that is an integer in the range of either `i64` or `u64` is
+    /// accepted, no matter how it's written, so `1e3` becomes `"1000"`.
+    /// Errors with `Error::IntegerOutOfRange` if the number has a fraction or
+    /// doesn't fit, and with `Error::WrongType` for non-numbers.
+    ///
+    /// ```
+    /// let id = json::parse("9007199254740993").unwrap();
+    ///
+    /// assert_eq!(id.as_integer_string().unwrap(), "9007199254740993");
+    /// assert_eq!(id.as_f64(), Some(9007199254740992.0));
+    /// ```
+    pub fn as_integer_string(&self) -> Result<String> {
+        let number = match self.as_number() {
+            Some(number) => number,
+            None         => return Err(Error::wrong_type("Number")),
+        };
+
+        let (positive, mantissa, mut exponent) = number.as_parts();
+        let limit = if positive { u64::MAX as u128 } else { 1u128 << 63 };
+        let mut magnitude = mantissa as u128;
+
+        if number.is_nan() {
+            return Err(Error::IntegerOutOfRange(self.dump()));
+        }
+
+        while exponent < 0 {
+            if !magnitude.is_multiple_of(10) {
+                return Err(Error::IntegerOutOfRange(self.dump()));
+            }
+
+            magnitude /= 10;
+            exponent += 1;
+        }
+
+        while exponent > 0 && magnitude != 0 {
+            magnitude *= 10;
+            exponent -= 1;
+
+            if magnitude > limit {
+                return Err(Error::IntegerOutOfRange(self.dump()));
+            }
+        }
+
+        if magnitude > limit {
+            return Err(Error::IntegerOutOfRange(self.dump()));
+        }
+
+        if positive || magnitude == 0 {
+            Ok(magnitude.to_string())
+        } else {
+            Ok(format!("-{}", magnitude))
+        }
+    }
+
     pub fn as_i32(&self) -> Option<i32> {
         self.as_i64().and_then(|value| number_to_signed!(i32, value, i64))
     }
 
+    /// Same as `as_u32_checked`, for `i32`.
+    pub fn as_i32_checked(&self) -> Result<i32> {
+        self.as_integer_in_range(i32::MIN as f64, i32::MAX as f64).map(|value| value as i32)
+    }
+
     pub fn as_i16(&self) -> Option<i16> {
         self.as_i64().and_then(|value| number_to_signed!(i16, value, i64))
     }
@@ -277,6 +1057,53 @@ impl JsonValue {
         }
     }
 
+    /// Interpret a value as a boolean, leniently. This is useful for flags
+    /// in configuration that may arrive in a number of shapes:
+    ///
+    /// | Value                             | Result          |
+    /// |-----------------------------------|-----------------|
+    /// | `true` / `false`                  | itself          |
+    /// | Number                            | `true` unless 0 |
+    /// | NaN                               | error           |
+    /// | `"true"`, `"1"`, `"yes"`          | `true`          |
+    /// | `"false"`, `"0"`, `"no"`          | `false`         |
+    /// | Any other string, `null`, objects and arrays | error |
+    ///
+    /// Strings are matched case-insensitively. A NaN errors with
+    /// `Error::NonFiniteNumber`, any other error is `Error::WrongType`.
+    ///
+    /// ```
+    /// # use json::JsonValue;
+    /// assert_eq!(JsonValue::from("Yes").coerce_bool(), Ok(true));
+    /// assert_eq!(JsonValue::from(0).coerce_bool(), Ok(false));
+    /// assert!(JsonValue::from("maybe").coerce_bool().is_err());
+    /// ```
+    pub fn coerce_bool(&self) -> Result<bool> {
+        match *self {
+            JsonValue::Boolean(value) => Ok(value),
+            JsonValue::Number(ref value) if value.is_nan() => Err(Error::NonFiniteNumber),
+            JsonValue::Number(ref value) => Ok(!value.is_zero()),
+            JsonValue::Short(ref value) if value.is_raw_number() => {
+                match self.as_number() {
+                    Some(number) if !number.is_nan() => Ok(!number.is_zero()),
+                    _ => Err(Error::NonFiniteNumber),
+                }
+            },
+            JsonValue::Short(_) | JsonValue::String(_) => {
+                let value = self.as_str().unwrap_or("");
+
+                if ["true", "1", "yes"].iter().any(|s| value.eq_ignore_ascii_case(s)) {
+                    Ok(true)
+                } else if ["false", "0", "no"].iter().any(|s| value.eq_ignore_ascii_case(s)) {
+                    Ok(false)
+                } else {
+                    Err(Error::wrong_type("Boolean"))
+                }
+            },
+            _ => Err(Error::wrong_type("Boolean"))
+        }
+    }
+
     /// Obtain an integer at a fixed decimal point. This is useful for
     /// converting monetary values and doing arithmetic on them without
     /// rounding errors introduced by floating point operations.
@@ -295,10 +1122,7 @@ impl JsonValue {
     /// assert_eq!(price_c.as_fixed_point_u64(2), Some(1020));
     /// ```
     pub fn as_fixed_point_u64(&self, point: u16) -> Option<u64> {
-        match *self {
-            JsonValue::Number(ref value) => value.as_fixed_point_u64(point),
-            _                            => None
-        }
+        self.as_number().and_then(|value| value.as_fixed_point_u64(point))
     }
 
     /// Analog to `as_fixed_point_u64`, except returning a signed
@@ -313,10 +1137,7 @@ impl JsonValue {
     /// assert_eq!(balance_b.as_fixed_point_i64(2), Some(4200));
     /// ```
     pub fn as_fixed_point_i64(&self, point: u16) -> Option<i64> {
-        match *self {
-            JsonValue::Number(ref value) => value.as_fixed_point_i64(point),
-            _                            => None
-        }
+        self.as_number().and_then(|value| value.as_fixed_point_i64(point))
     }
 
     /// Take over the ownership of the value, leaving `Null` in it's place.
@@ -342,6 +1163,27 @@ impl JsonValue {
         mem::replace(self, JsonValue::Null)
     }
 
+    /// Take ownership of the value, replace it with the result of `f`. Should
+    /// `f` fail, the value is left as `Null` and the error is returned, the
+    /// original value has been moved into the closure and is gone.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = object!{ port: "8080" };
+    ///
+    /// data["port"].replace_with(|value| json::parse(value.as_str().unwrap_or(""))).unwrap();
+    ///
+    /// assert_eq!(data, object!{ port: 8080 });
+    /// # }
+    /// ```
+    pub fn replace_with<F>(&mut self, f: F) -> Result<()>
+    where F: FnOnce(JsonValue) -> Result<JsonValue> {
+        *self = f(self.take())?;
+
+        Ok(())
+    }
+
     /// Checks that self is a string, returns an owned Rust `String`, leaving
     /// `Null` in it's place.
     ///
@@ -370,7 +1212,7 @@ impl JsonValue {
         mem::swap(self, &mut placeholder);
 
         match placeholder {
-            JsonValue::Short(short)   => return Some(short.into()),
+            JsonValue::Short(short) if !short.is_raw_number() => return Some(short.into()),
             JsonValue::String(string) => return Some(string),
 
             // Not a string? Swap the original value back in place!
@@ -392,6 +1234,75 @@ impl JsonValue {
         }
     }
 
+    /// Same as `push`, but refuses to grow the whole value beyond `max_nodes`
+    /// values, as counted by `node_count`, erroring with
+    /// `Error::ExceededNodeLimit` instead. This is meant for bounded
+    /// accumulators, note that every call counts all nodes of the value,
+    /// so it takes time proportional to its size.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = array![1];
+    ///
+    /// assert!(data.push_checked(2, 3).is_ok());
+    /// assert_eq!(data.push_checked(3, 3), Err(json::Error::ExceededNodeLimit(3)));
+    /// assert_eq!(data, array![1, 2]);
+    /// # }
+    /// ```
+    pub fn push_checked<T>(&mut self, value: T, max_nodes: usize) -> Result<()>
+    where T: Into<JsonValue> {
+        if !self.is_array() {
+            return Err(Error::wrong_type("Array"));
+        }
+
+        let value = value.into();
+
+        if self.node_count() + value.node_count() > max_nodes {
+            return Err(Error::ExceededNodeLimit(max_nodes));
+        }
+
+        self.push(value)
+    }
+
+    /// Count all values in this value, including itself, so a primitive
+    /// counts as 1, and `[1, [2]]` as 4. This is the same way the node limit
+    /// is counted by `json::parse_with_limits`.
+    pub fn node_count(&self) -> usize {
+        match *self {
+            JsonValue::Array(ref vec) => {
+                vec.iter().fold(1, |count, member| count + member.node_count())
+            },
+            JsonValue::Object(ref object) => {
+                object.iter().fold(1, |count, (_, value)| count + value.node_count())
+            },
+            _ => 1,
+        }
+    }
+
+    /// Alias of `node_count`, to go along with `depth`.
+    #[inline]
+    pub fn count_nodes(&self) -> usize {
+        self.node_count()
+    }
+
+    /// Get the maximum nesting level of this value. Primitives are at depth
+    /// 0, and every array or object adds one level on top of its deepest
+    /// member, so both `[]` and `[1]` are at depth 1, and `{"a":[2]}` at 2.
+    /// Together with `count_nodes` this is handy for checking an already
+    /// parsed value against size limits.
+    pub fn depth(&self) -> usize {
+        match *self {
+            JsonValue::Array(ref vec) => {
+                1 + vec.iter().map(JsonValue::depth).max().unwrap_or(0)
+            },
+            JsonValue::Object(ref object) => {
+                1 + object.iter().map(|(_, value)| value.depth()).max().unwrap_or(0)
+            },
+            _ => 0,
+        }
+    }
+
     /// Works on `JsonValue::Array` - remove and return last element from
     /// an array. On failure returns a null.
     pub fn pop(&mut self) -> JsonValue {
@@ -411,6 +1322,30 @@ impl JsonValue {
         }
     }
 
+    /// Checks whether `needle` is equal to this value, or to any value nested
+    /// within it, at any depth.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ errors: [{ code: 404, message: "Not found" }] };
+    ///
+    /// assert!(data.contains_subtree(&object!{ code: 404, message: "Not found" }));
+    /// assert!(!data.contains_subtree(&object!{ code: 404 }));
+    /// # }
+    /// ```
+    pub fn contains_subtree(&self, needle: &JsonValue) -> bool {
+        if self == needle {
+            return true;
+        }
+
+        match *self {
+            JsonValue::Array(ref vec) => vec.iter().any(|member| member.contains_subtree(needle)),
+            JsonValue::Object(ref object) => object.iter().any(|(_, value)| value.contains_subtree(needle)),
+            _ => false,
+        }
+    }
+
     /// Works on `JsonValue::Object` - checks if the object has a key
     pub fn has_key(&self, key: &str) -> bool {
         match *self {
@@ -419,112 +1354,783 @@ impl JsonValue {
         }
     }
 
-    /// Returns length of array or object (number of keys), defaults to `0` for
-    /// other types.
-    pub fn len(&self) -> usize {
-        match *self {
-            JsonValue::Array(ref vec) => {
-                vec.len()
+    /// Works on `JsonValue::Object` - checks if the object has a key, and
+    /// the value under it is a string.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ name: "Jane", age: 30 };
+    ///
+    /// assert!(data.has_string("name"));
+    /// assert!(!data.has_string("age"));
+    /// assert!(!data.has_string("email"));
+    /// # }
+    /// ```
+    pub fn has_string(&self, key: &str) -> bool {
+        self[key].is_string()
+    }
+
+    /// Works on `JsonValue::Object` - checks if the object has a key, and
+    /// the value under it is a number.
+    pub fn has_number(&self, key: &str) -> bool {
+        self[key].is_number()
+    }
+
+    /// Works on `JsonValue::Object` - checks if the object has a key, and
+    /// the value under it is a boolean.
+    pub fn has_boolean(&self, key: &str) -> bool {
+        self[key].is_boolean()
+    }
+
+    /// Works on `JsonValue::Object` - checks if the object has a key, and
+    /// the value under it is an array.
+    pub fn has_array(&self, key: &str) -> bool {
+        self[key].is_array()
+    }
+
+    /// Works on `JsonValue::Object` - checks if the object has a key, and
+    /// the value under it is an object.
+    pub fn has_object(&self, key: &str) -> bool {
+        self[key].is_object()
+    }
+
+    /// Recursively compare two values, treating a key missing from an object
+    /// the same as a key that is present with a `null` value. Arrays are still
+    /// compared positionally, so a `null` member of an array is significant.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let a = object!{ a: 1, nested: { c: null } };
+    /// let b = object!{ a: 1, b: null, nested: {} };
+    ///
+    /// assert!(a != b);
+    /// assert!(a.eq_ignore_nulls(&b));
+    /// # }
+    /// ```
+    pub fn eq_ignore_nulls(&self, other: &JsonValue) -> bool {
+        match (self, other) {
+            (JsonValue::Object(a), JsonValue::Object(b)) => {
+                a.iter().all(|(key, value)| value.eq_ignore_nulls(&b[key])) &&
+                b.iter().all(|(key, value)| value.is_null() || a.get(key).is_some())
             },
-            JsonValue::Object(ref object) => {
-                object.len()
+            (JsonValue::Array(a), JsonValue::Array(b)) => {
+                a.len() == b.len() &&
+                a.iter().zip(b.iter()).all(|(a, b)| a.eq_ignore_nulls(b))
             },
-            _ => 0
+            _ => self == other,
         }
     }
 
-    /// Works on `JsonValue::Array` - returns an iterator over members.
-    /// Will return an empty iterator if called on non-array types.
-    /// ## Example
+    /// Check that this value matches a partial `pattern`, which is handy for
+    /// asserting on parts of a larger document in tests:
+    ///
+    /// - an object matches if every key of the pattern is present, with a
+    ///   matching value, any other keys are ignored
+    /// - an array matches if it has the same length as the pattern, and all
+    ///   members match positionally
+    /// - a `null` in the pattern matches anything, including a missing key,
+    ///   so there is no way to require an actual `null`
+    /// - everything else has to be equal
+    ///
+    /// Errors with `Error::PatternMismatch` describing the first mismatch,
+    /// with the JSON Pointer to it, or with `Error::UndefinedField` carrying
+    /// the pointer to a key that is missing.
+    ///
     /// ```
-    /// # use json::JsonValue;
-    /// # #[macro_use] use json::array;
-    /// let animals = array!["Cat", "Dog", "Snail"];
-    /// let mut animals_with_letter_a = Vec::new();
-    /// for animal in animals.members() {
-    ///     if animal.as_str().unwrap().contains('a') {
-    ///         animals_with_letter_a.push(animal);
-    ///     }
-    /// }
-    /// assert_eq!(animals_with_letter_a, vec!["Cat", "Snail"]);
+    /// # #[macro_use] extern crate json;
+    /// # use json::Error;
+    /// # fn main() {
+    /// let response = object!{ status: 200, body: { id: 7, tags: ["a"] } };
+    ///
+    /// assert!(response.assert_matches(&object!{ body: { id: null, tags: ["a"] } }).is_ok());
+    /// assert_eq!(response.assert_matches(&object!{ status: 404 }), Err(Error::PatternMismatch {
+    ///     pointer: "/status".into(),
+    ///     expected: "404".into(),
+    ///     found: "200".into(),
+    /// }));
+    /// # }
     /// ```
-    pub fn members(&self) -> Members {
-        match *self {
-            JsonValue::Array(ref vec) => {
-                vec.iter()
-            },
-            _ => [].iter()
-        }
+    pub fn assert_matches(&self, pattern: &JsonValue) -> Result<()> {
+        self.match_pattern(pattern, &mut String::new())
     }
 
-    /// Works on `JsonValue::Array` - returns a mutable iterator over members.
-    /// Will return an empty iterator if called on non-array types.
-    pub fn members_mut(&mut self) -> MembersMut {
-        match *self {
-            JsonValue::Array(ref mut vec) => {
-                vec.iter_mut()
+    fn match_pattern(&self, pattern: &JsonValue, pointer: &mut String) -> Result<()> {
+        let len = pointer.len();
+
+        match (self, pattern) {
+            (_, JsonValue::Null) => return Ok(()),
+            (JsonValue::Object(object), JsonValue::Object(pattern)) => {
+                for (key, pattern) in pattern.iter() {
+                    pointer.push('/');
+                    pointer::escape_token(pointer, key);
+
+                    match object.get(key) {
+                        Some(value) => value.match_pattern(pattern, pointer)?,
+                        None if pattern.is_null() => {},
+                        None => return Err(Error::undefined(pointer)),
+                    }
+
+                    pointer.truncate(len);
+                }
+
+                return Ok(());
             },
-            _ => [].iter_mut()
+            (JsonValue::Array(vec), JsonValue::Array(pattern)) if vec.len() == pattern.len() => {
+                for (index, (value, pattern)) in vec.iter().zip(pattern.iter()).enumerate() {
+                    pointer.push('/');
+                    pointer.push_str(&index.to_string());
+                    value.match_pattern(pattern, pointer)?;
+                    pointer.truncate(len);
+                }
+
+                return Ok(());
+            },
+            _ => if self == pattern {
+                return Ok(());
+            }
         }
+
+        Err(Error::PatternMismatch {
+            pointer: pointer.clone(),
+            expected: pattern.dump(),
+            found: self.dump(),
+        })
     }
 
-    /// Works on `JsonValue::Object` - returns an iterator over key value pairs.
-    /// Will return an empty iterator if called on non-object types.
-    /// ## Example
+    /// Works on `JsonValue::Object` - compare the top level keys of two
+    /// objects, returning the names of keys that were `(added, removed,
+    /// changed)` in `other` relative to `self`. A key is changed if it's
+    /// present in both objects with unequal values. Added keys are listed in
+    /// the order of `other`, the rest in the order of `self`. Errors if either
+    /// value is not an object.
+    ///
     /// ```
-    /// # use json::JsonValue;
-    /// let heights = json::parse(r#"
-    /// {
-    ///     "Alice": 1.42,
-    ///     "Bob": 1.6,
-    ///     "Carlos": 2.1
-    /// }
-    /// "#).unwrap();
-    /// let mut total_height = 0.0;
-    /// let mut names_with_o: Vec<&str> = Vec::new();
-    /// for (name, height) in heights.entries() {
-    ///     total_height += height.as_f64().unwrap();
-    ///     if name.contains('o') {
-    ///         names_with_o.push(name);
-    ///     }
-    /// }
-    /// assert_eq!(total_height, 5.12);
-    /// assert_eq!(names_with_o, vec!["Bob", "Carlos"]);
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let old = object!{ a: 1, b: 2, c: 3 };
+    /// let new = object!{ a: 1, c: 4, d: 5 };
+    ///
+    /// let (added, removed, changed) = old.diff_keys(&new).unwrap();
+    ///
+    /// assert_eq!(added, ["d"]);
+    /// assert_eq!(removed, ["b"]);
+    /// assert_eq!(changed, ["c"]);
+    /// # }
     /// ```
-    pub fn entries(&self) -> Entries {
-        match *self {
-            JsonValue::Object(ref object) => {
-                object.iter()
-            },
-            _ => Entries::empty()
+    pub fn diff_keys(&self, other: &JsonValue) -> Result<(Vec<String>, Vec<String>, Vec<String>)> {
+        let (a, b) = match (self, other) {
+            (JsonValue::Object(a), JsonValue::Object(b)) => (a, b),
+            _ => return Err(Error::wrong_type("Object")),
+        };
+
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        let mut changed = Vec::new();
+
+        for (key, value) in a.iter() {
+            match b.get(key) {
+                Some(other) => if value != other {
+                    changed.push(key.to_string());
+                },
+                None => removed.push(key.to_string()),
+            }
         }
-    }
 
-    /// Works on `JsonValue::Object` - returns a mutable iterator over
-    /// key value pairs.
-    /// Will return an empty iterator if called on non-object types.
-    pub fn entries_mut(&mut self) -> EntriesMut {
-        match *self {
-            JsonValue::Object(ref mut object) => {
-                object.iter_mut()
-            },
-            _ => EntriesMut::empty()
+        for (key, _) in b.iter() {
+            if a.get(key).is_none() {
+                added.push(key.to_string());
+            }
         }
-    }
 
-    /// Works on `JsonValue::Object` - inserts a new entry, or override an existing
-    /// one into the object. Note that `key` has to be a `&str` slice and not an owned
-    /// `String`. The internals of `Object` will handle the heap allocation of the key
-    /// if needed for better performance.
-    pub fn insert<T>(&mut self, key: &str, value: T) -> Result<()>
-    where T: Into<JsonValue> {
-        match *self {
-            JsonValue::Object(ref mut object) => {
-                object.insert(key, value.into());
-                Ok(())
-            },
-            _ => Err(Error::wrong_type("Object"))
-        }
+        Ok((added, removed, changed))
+    }
+
+    /// Works on `JsonValue::Object` - pair up the top level values of two
+    /// objects. The result has every key present in either object, in the
+    /// order of `self` followed by keys only present in `other`, each one
+    /// holding a two element array of `[self_value, other_value]`, with a
+    /// null standing in for a missing side. Errors if either value is not an
+    /// object.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let old = object!{ a: 1, b: 2 };
+    /// let new = object!{ b: 3, c: 4 };
+    ///
+    /// assert_eq!(old.zip(&new).unwrap(), object!{
+    ///     a: [1, null],
+    ///     b: [2, 3],
+    ///     c: [null, 4]
+    /// });
+    /// # }
+    /// ```
+    pub fn zip(&self, other: &JsonValue) -> Result<JsonValue> {
+        let (a, b) = match (self, other) {
+            (JsonValue::Object(a), JsonValue::Object(b)) => (a, b),
+            _ => return Err(Error::wrong_type("Object")),
+        };
+
+        let mut zipped = Object::with_capacity(a.len());
+
+        for (key, value) in a.iter() {
+            zipped.insert(key, JsonValue::Array(vec![value.clone(), b[key].clone()]));
+        }
+
+        for (key, value) in b.iter() {
+            if a.get(key).is_none() {
+                zipped.insert(key, JsonValue::Array(vec![JsonValue::Null, value.clone()]));
+            }
+        }
+
+        Ok(JsonValue::Object(zipped))
+    }
+
+    /// Works on `JsonValue::Array` - pair up the members of two arrays by
+    /// index into an array of two element arrays of `[self_member,
+    /// other_member]`. The result is as long as the shorter of the two, the
+    /// remaining members of the longer one are ignored. Errors if either
+    /// value is not an array.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let names = array!["Jane", "John"];
+    /// let ages = array![31, 42];
+    ///
+    /// assert_eq!(names.zip_arrays(&ages).unwrap(), array![["Jane", 31], ["John", 42]]);
+    /// # }
+    /// ```
+    pub fn zip_arrays(&self, other: &JsonValue) -> Result<JsonValue> {
+        match (self, other) {
+            (JsonValue::Array(a), JsonValue::Array(b)) => {
+                Ok(JsonValue::Array(
+                    a.iter().zip(b).map(|(a, b)| JsonValue::Array(vec![a.clone(), b.clone()])).collect()
+                ))
+            },
+            _ => Err(Error::wrong_type("Array")),
+        }
+    }
+
+    /// Works on `JsonValue::Object` - get a reference to the value behind
+    /// `key`. Returns `None` if the key is missing or if called on anything
+    /// but an object, which composes nicely with `?` and `Option` combinators.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # use json::JsonValue;
+    /// # fn main() {
+    /// fn first_tag(data: &JsonValue) -> Option<&str> {
+    ///     data.try_get("tags")?.try_at(0)?.as_str()
+    /// }
+    ///
+    /// assert_eq!(first_tag(&object!{ tags: ["foo", "bar"] }), Some("foo"));
+    /// assert_eq!(first_tag(&object!{ tags: [] }), None);
+    /// assert_eq!(first_tag(&array![]), None);
+    /// # }
+    /// ```
+    pub fn try_get(&self, key: &str) -> Option<&JsonValue> {
+        match *self {
+            JsonValue::Object(ref object) => object.get(key),
+            _                             => None
+        }
+    }
+
+    /// Works on `JsonValue::Object` - get a reference to the value behind a
+    /// key matching `key` case-insensitively (ASCII only). If more than one
+    /// key matches, the first one in sorted order wins, regardless of the
+    /// order of insertion. Errors with `Error::UndefinedField` if no key
+    /// matches, and with `Error::WrongType` if called on anything but an
+    /// object.
+    ///
+    /// **Note:** unlike indexing, this is O(n) as it has to look at every key.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ userid: 42 };
+    ///
+    /// assert_eq!(data.get_ci("UserID").unwrap(), 42);
+    /// # }
+    /// ```
+    pub fn get_ci(&self, key: &str) -> Result<&JsonValue> {
+        match *self {
+            JsonValue::Object(ref object) => {
+                object.iter()
+                      .filter(|entry| entry.0.eq_ignore_ascii_case(key))
+                      .min_by(|a, b| a.0.cmp(b.0))
+                      .map(|entry| entry.1)
+                      .ok_or_else(|| Error::undefined(key))
+            },
+            _ => Err(Error::wrong_type("Object"))
+        }
+    }
+
+    /// Works on `JsonValue::Object` - get references to the values behind
+    /// all of `keys` at once, in the same order as the keys. Errors with
+    /// `Error::UndefinedField` on the first key that's missing, and with
+    /// `Error::WrongType` if called on anything but an object.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ host: "localhost", port: 8080, tls: false };
+    ///
+    /// if let [host, port] = data.get_many(&["host", "port"]).unwrap()[..] {
+    ///     assert_eq!(host, "localhost");
+    ///     assert_eq!(port, 8080);
+    /// }
+    /// # }
+    /// ```
+    pub fn get_many<'a>(&'a self, keys: &[&str]) -> Result<Vec<&'a JsonValue>> {
+        match *self {
+            JsonValue::Object(ref object) => {
+                keys.iter()
+                    .map(|key| object.get(key).ok_or_else(|| Error::undefined(key)))
+                    .collect()
+            },
+            _ => Err(Error::wrong_type("Object"))
+        }
+    }
+
+    /// Works on `JsonValue::Object` - unwrap a conventional API envelope,
+    /// such as `{"ok": true, "data": ...}` or `{"ok": false, "error": ...}`.
+    /// If the boolean behind `ok_key` is `true`, returns a reference to the
+    /// value behind `data_key`, which is a null if it's missing. Otherwise
+    /// errors with `Error::ErrorResponse` carrying the value behind
+    /// `error_key`, as per `to_display_string`.
+    ///
+    /// Errors with `Error::UndefinedField` if `ok_key` is missing, and with
+    /// `Error::WrongType` if it's not a boolean, or if called on anything
+    /// but an object.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let success = object!{ ok: true, data: { id: 1 } };
+    /// let failure = object!{ ok: false, error: "Not found" };
+    ///
+    /// assert_eq!(success.as_result("ok", "data", "error").unwrap(), &object!{ id: 1 });
+    /// assert_eq!(
+    ///     failure.as_result("ok", "data", "error"),
+    ///     Err(json::Error::ErrorResponse("Not found".into()))
+    /// );
+    /// # }
+    /// ```
+    pub fn as_result(&self, ok_key: &str, data_key: &str, error_key: &str) -> Result<&JsonValue> {
+        let object = match *self {
+            JsonValue::Object(ref object) => object,
+            _ => return Err(Error::wrong_type("Object")),
+        };
+
+        match object.get(ok_key) {
+            Some(&JsonValue::Boolean(true))  => Ok(object.get(data_key).unwrap_or(&NULL)),
+            Some(&JsonValue::Boolean(false)) => Err(Error::ErrorResponse(self[error_key].to_display_string())),
+            Some(_) => Err(Error::wrong_type(&format!("Boolean at {:?}", ok_key))),
+            None    => Err(Error::undefined(ok_key)),
+        }
+    }
+
+    /// Works on `JsonValue::Array` - get a reference to the member at `index`.
+    /// Returns `None` if the index is out of bounds or if called on anything
+    /// but an array.
+    pub fn try_at(&self, index: usize) -> Option<&JsonValue> {
+        match *self {
+            JsonValue::Array(ref vec) => vec.get(index),
+            _                         => None
+        }
+    }
+
+    /// Collect every value reachable through a dot separated `path`, such as
+    /// `users.*.email`, in document order. A `*` segment matches every member
+    /// of an array or every value of an object, a segment of digits matches
+    /// the array member at that index, and any other segment matches the
+    /// object value under that key. Paths that don't resolve are skipped, so
+    /// the result may be empty. An empty path matches the value itself.
+    ///
+    /// **Note:** keys containing a `.`, or that are a literal `*`, can't be
+    /// addressed, use `pointer` for those.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{
+    ///     users: [
+    ///         { name: "Jane", email: "jane@example.com" },
+    ///         { name: "John" },
+    ///         { name: "Joe", email: "joe@example.com" }
+    ///     ]
+    /// };
+    ///
+    /// assert_eq!(data.query("users.*.email"), ["jane@example.com", "joe@example.com"]);
+    /// assert_eq!(data.query("users.1.name"), ["John"]);
+    /// # }
+    /// ```
+    pub fn query(&self, path: &str) -> Vec<&JsonValue> {
+        let mut current = vec![self];
+
+        if path.is_empty() {
+            return current;
+        }
+
+        for segment in path.split('.') {
+            let mut next = Vec::new();
+
+            for value in current {
+                match (value, segment) {
+                    (JsonValue::Array(vec), "*") => next.extend(vec.iter()),
+                    (JsonValue::Object(object), "*") => next.extend(object.iter().map(|(_, value)| value)),
+                    (JsonValue::Array(vec), _) => {
+                        if let Some(member) = pointer::parse_index(segment).and_then(|index| vec.get(index)) {
+                            next.push(member);
+                        }
+                    },
+                    (JsonValue::Object(object), _) => next.extend(object.get(segment)),
+                    _ => {}
+                }
+            }
+
+            current = next;
+        }
+
+        current
+    }
+
+    /// Returns length of array or object (number of keys), defaults to `0` for
+    /// other types.
+    pub fn len(&self) -> usize {
+        match *self {
+            JsonValue::Array(ref vec) => {
+                vec.len()
+            },
+            JsonValue::Object(ref object) => {
+                object.len()
+            },
+            _ => 0
+        }
+    }
+
+    /// Works on `JsonValue::Array` - returns an iterator over members.
+    /// Will return an empty iterator if called on non-array types.
+    /// ## Example
+    /// ```
+    /// # use json::JsonValue;
+    /// # #[macro_use] use json::array;
+    /// let animals = array!["Cat", "Dog", "Snail"];
+    /// let mut animals_with_letter_a = Vec::new();
+    /// for animal in animals.members() {
+    ///     if animal.as_str().unwrap().contains('a') {
+    ///         animals_with_letter_a.push(animal);
+    ///     }
+    /// }
+    /// assert_eq!(animals_with_letter_a, vec!["Cat", "Snail"]);
+    /// ```
+    pub fn members(&self) -> Members {
+        match *self {
+            JsonValue::Array(ref vec) => {
+                vec.iter()
+            },
+            _ => [].iter()
+        }
+    }
+
+    /// Iterate over a field that can hold either one value or an array of
+    /// them, without normalizing it first, see `as_array_lenient`. Yields the
+    /// members of an array, nothing for `null`, and the value itself for
+    /// anything else.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ one: "rust", many: ["json", "serde"] };
+    ///
+    /// let tags: Vec<_> = data["one"].members_lenient()
+    ///                        .chain(data["many"].members_lenient())
+    ///                        .chain(data["none"].members_lenient())
+    ///                        .collect();
+    ///
+    /// assert_eq!(tags, ["rust", "json", "serde"]);
+    /// # }
+    /// ```
+    pub fn members_lenient(&self) -> Members<'_> {
+        match *self {
+            JsonValue::Array(ref vec) => vec.iter(),
+            JsonValue::Null           => [].iter(),
+            _                         => ::std::slice::from_ref(self).iter(),
+        }
+    }
+
+    /// Works on `JsonValue::Array` - returns a mutable iterator over members.
+    /// Will return an empty iterator if called on non-array types.
+    pub fn members_mut(&mut self) -> MembersMut {
+        match *self {
+            JsonValue::Array(ref mut vec) => {
+                vec.iter_mut()
+            },
+            _ => [].iter_mut()
+        }
+    }
+
+    /// Works on `JsonValue::Object` - returns an iterator over key value pairs.
+    /// Will return an empty iterator if called on non-object types.
+    /// ## Example
+    /// ```
+    /// # use json::JsonValue;
+    /// let heights = json::parse(r#"
+    /// {
+    ///     "Alice": 1.42,
+    ///     "Bob": 1.6,
+    ///     "Carlos": 2.1
+    /// }
+    /// "#).unwrap();
+    /// let mut total_height = 0.0;
+    /// let mut names_with_o: Vec<&str> = Vec::new();
+    /// for (name, height) in heights.entries() {
+    ///     total_height += height.as_f64().unwrap();
+    ///     if name.contains('o') {
+    ///         names_with_o.push(name);
+    ///     }
+    /// }
+    /// assert_eq!(total_height, 5.12);
+    /// assert_eq!(names_with_o, vec!["Bob", "Carlos"]);
+    /// ```
+    pub fn entries(&self) -> Entries {
+        match *self {
+            JsonValue::Object(ref object) => {
+                object.iter()
+            },
+            _ => Entries::empty()
+        }
+    }
+
+    /// Works on `JsonValue::Object` - returns a mutable iterator over
+    /// key value pairs.
+    /// Will return an empty iterator if called on non-object types.
+    pub fn entries_mut(&mut self) -> EntriesMut {
+        match *self {
+            JsonValue::Object(ref mut object) => {
+                object.iter_mut()
+            },
+            _ => EntriesMut::empty()
+        }
+    }
+
+    /// Total ordering of values, which makes it possible to sort them, also
+    /// used for `PartialOrd`. Values of different kinds are ordered as
+    /// follows:
+    ///
+    /// `null` < booleans < numbers < strings < arrays < objects
+    ///
+    /// Numbers are compared exactly, as per `Number::total_cmp`, so NaN is
+    /// greater than all other numbers and equal to itself. Raw numbers are
+    /// ordered by their value, same as regular numbers. Strings compare
+    /// lexicographically by bytes and arrays by their members. Objects are
+    /// compared as their entries sorted by key, since the order of keys doesn't
+    /// matter for equality. This is also the `Ord` implementation, which
+    /// makes it possible to use values in a `BTreeSet` or as `BTreeMap` keys.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut values = vec![json::from("b"), json::from(2), json::Null, json::from("a"), json::from(true)];
+    ///
+    /// values.sort_by(json::JsonValue::total_cmp);
+    ///
+    /// assert_eq!(values, vec![json::Null, json::from(true), json::from(2), json::from("a"), json::from("b")]);
+    /// # }
+    /// ```
+    pub fn total_cmp(&self, other: &JsonValue) -> Ordering {
+        use self::JsonValue::*;
+
+        fn rank(value: &JsonValue) -> u8 {
+            match *value {
+                Null                 => 0,
+                Boolean(_)           => 1,
+                Number(_)            => 2,
+                Short(_) | String(_) => if value.is_number() { 2 } else { 3 },
+                Array(_)             => 4,
+                Object(_)            => 5,
+            }
+        }
+
+        match (self, other) {
+            (Boolean(a), Boolean(b)) => a.cmp(b),
+            (Array(a), Array(b)) => {
+                a.iter()
+                 .zip(b.iter())
+                 .map(|(a, b)| a.total_cmp(b))
+                 .find(|ordering| *ordering != Ordering::Equal)
+                 .unwrap_or_else(|| a.len().cmp(&b.len()))
+            },
+            (Object(a), Object(b)) => {
+                let mut a: Vec<_> = a.iter().collect();
+                let mut b: Vec<_> = b.iter().collect();
+
+                a.sort_by(|a, b| a.0.cmp(b.0));
+                b.sort_by(|a, b| a.0.cmp(b.0));
+
+                a.iter()
+                 .zip(b.iter())
+                 .map(|(a, b)| a.0.cmp(b.0).then_with(|| a.1.total_cmp(b.1)))
+                 .find(|ordering| *ordering != Ordering::Equal)
+                 .unwrap_or_else(|| a.len().cmp(&b.len()))
+            },
+            _ => {
+                if let (Some(a), Some(b)) = (self.as_number(), other.as_number()) {
+                    return a.total_cmp(&b);
+                }
+
+                match (self.as_str(), other.as_str()) {
+                    (Some(a), Some(b)) => a.cmp(b),
+                    _                  => rank(self).cmp(&rank(other)),
+                }
+            }
+        }
+    }
+
+    /// Works on `JsonValue::Object` - collect all entries sorted in ascending
+    /// order of their values, with ties broken by key. Numbers, strings and
+    /// booleans can be ordered among values of their own kind only, so this
+    /// errors if the object holds a mix of those, or any objects, arrays,
+    /// nulls, or NaN numbers. Also errors if called on anything but an object.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let scores = object!{ a: 3, b: 1, c: 2 };
+    ///
+    /// let keys: Vec<&str> = scores.entries_sorted_by_value().unwrap()
+    ///                             .into_iter()
+    ///                             .map(|(key, _)| key)
+    ///                             .collect();
+    ///
+    /// assert_eq!(keys, ["b", "c", "a"]);
+    /// # }
+    /// ```
+    pub fn entries_sorted_by_value(&self) -> Result<Vec<(&str, &JsonValue)>> {
+        let object = match *self {
+            JsonValue::Object(ref object) => object,
+            _ => return Err(Error::wrong_type("Object")),
+        };
+
+        let mut entries: Vec<_> = object.iter().collect();
+
+        // Every value has to be orderable against the first one, so they
+        // are all of one kind and none of them is NaN
+        let comparable = match entries.first() {
+            Some(&(_, first)) => entries.iter().all(|&(_, value)| {
+                compare_values(first, value).is_some()
+            }),
+            None => true,
+        };
+
+        if comparable {
+            entries.sort_by(|a, b| total_compare(a.1, b.1).then_with(|| a.0.cmp(b.0)));
+
+            Ok(entries)
+        } else {
+            Err(Error::wrong_type("Numbers, Strings or Booleans of one kind"))
+        }
+    }
+
+    /// Works on `JsonValue::Object` - inserts a new entry, or override an existing
+    /// one into the object. Note that `key` has to be a `&str` slice and not an owned
+    /// `String`. The internals of `Object` will handle the heap allocation of the key
+    /// if needed for better performance.
+    pub fn insert<T>(&mut self, key: &str, value: T) -> Result<()>
+    where T: Into<JsonValue> {
+        match *self {
+            JsonValue::Object(ref mut object) => {
+                object.insert(key, value.into());
+                Ok(())
+            },
+            _ => Err(Error::wrong_type("Object"))
+        }
+    }
+
+    /// Works on `JsonValue::Object` - get a mutable reference to the array
+    /// behind `key`, creating an empty one if the key is missing or holds
+    /// `Null`. Errors if the key holds anything else, or if called on
+    /// anything but an object.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = object!{};
+    ///
+    /// data.array_at("items").unwrap().push(1).unwrap();
+    /// data.array_at("items").unwrap().push(2).unwrap();
+    ///
+    /// assert_eq!(data, object!{ items: [1, 2] });
+    /// # }
+    /// ```
+    pub fn array_at(&mut self, key: &str) -> Result<&mut JsonValue> {
+        let object = match *self {
+            JsonValue::Object(ref mut object) => object,
+            _ => return Err(Error::wrong_type("Object")),
+        };
+
+        match object.get(key) {
+            Some(&JsonValue::Array(_)) => {},
+            Some(&JsonValue::Null) | None => object.insert(key, JsonValue::new_array()),
+            Some(_) => return Err(Error::wrong_type(&format!("Array at {:?}", key))),
+        }
+
+        Ok(object.get_mut(key).expect("Was just checked or inserted"))
+    }
+
+    /// Same as `insert`, but refuses to grow the whole value beyond
+    /// `max_nodes` values, see `push_checked`. Should `key` already be
+    /// present, the nodes of the value it holds don't count, since it gets
+    /// replaced.
+    pub fn insert_checked<T>(&mut self, key: &str, value: T, max_nodes: usize) -> Result<()>
+    where T: Into<JsonValue> {
+        let replaced = match *self {
+            JsonValue::Object(ref object) => object.get(key).map(JsonValue::node_count).unwrap_or(0),
+            _ => return Err(Error::wrong_type("Object")),
+        };
+
+        let value = value.into();
+
+        if self.node_count() - replaced + value.node_count() > max_nodes {
+            return Err(Error::ExceededNodeLimit(max_nodes));
+        }
+
+        self.insert(key, value)
+    }
+
+    /// Works on `JsonValue::Object` - apply `f` to the value behind `key`,
+    /// modifying it in place. Errors if `key` is not present in the object,
+    /// or if called on anything but an object.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = object!{ tags: [] };
+    ///
+    /// data.update("tags", |tags| {
+    ///     tags.push("foo").unwrap();
+    /// }).unwrap();
+    ///
+    /// assert_eq!(data, object!{ tags: ["foo"] });
+    /// # }
+    /// ```
+    pub fn update<F>(&mut self, key: &str, f: F) -> Result<()>
+    where F: FnOnce(&mut JsonValue) {
+        match *self {
+            JsonValue::Object(ref mut object) => {
+                match object.get_mut(key) {
+                    Some(value) => {
+                        f(value);
+                        Ok(())
+                    },
+                    None => Err(Error::undefined(key))
+                }
+            },
+            _ => Err(Error::wrong_type("Object"))
+        }
     }
 
     /// Works on `JsonValue::Object` - remove a key and return the value it held.
@@ -533,9 +2139,340 @@ impl JsonValue {
     pub fn remove(&mut self, key: &str) -> JsonValue {
         match *self {
             JsonValue::Object(ref mut object) => {
-                object.remove(key).unwrap_or(JsonValue::Null)
+                object.remove(key).unwrap_or(JsonValue::Null)
+            },
+            _ => JsonValue::Null
+        }
+    }
+
+    /// Works on `JsonValue::Object` - remove every top level key that is not
+    /// listed in `keep`, preserving the order of the remaining entries.
+    /// Errors if called on anything but an object.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = object!{ id: 1, name: "foo", password: "hunter2" };
+    ///
+    /// data.retain_keys(&["id", "name"]).unwrap();
+    ///
+    /// assert_eq!(data, object!{ id: 1, name: "foo" });
+    /// # }
+    /// ```
+    pub fn retain_keys(&mut self, keep: &[&str]) -> Result<()> {
+        match *self {
+            JsonValue::Object(ref mut object) => {
+                let mut retained = Object::with_capacity(keep.len());
+
+                for (key, value) in object.iter_mut() {
+                    if keep.contains(&key) {
+                        retained.insert(key, value.take());
+                    }
+                }
+
+                *object = retained;
+                Ok(())
+            },
+            _ => Err(Error::wrong_type("Object"))
+        }
+    }
+
+    /// Works on `JsonValue::Object` - collect every top level key starting
+    /// with `prefix` into a new object, with the prefix stripped off, in the
+    /// same order. Values are cloned, and the original object is left as it
+    /// is. Errors if called on anything but an object.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let config = object!{ db_host: "localhost", db_port: 5432, web_port: 80 };
+    ///
+    /// assert_eq!(config.extract_prefix("db_").unwrap(), object!{ host: "localhost", port: 5432 });
+    /// # }
+    /// ```
+    pub fn extract_prefix(&self, prefix: &str) -> Result<JsonValue> {
+        match *self {
+            JsonValue::Object(ref object) => {
+                let mut extracted = Object::new();
+
+                for (key, value) in object.iter() {
+                    if let Some(key) = key.strip_prefix(prefix) {
+                        extracted.insert(key, value.clone());
+                    }
+                }
+
+                Ok(JsonValue::Object(extracted))
+            },
+            _ => Err(Error::wrong_type("Object"))
+        }
+    }
+
+    /// Works on `JsonValue::Object` - replace every key with the result of
+    /// calling `f` on it, keeping the order of entries. Only the top level
+    /// keys are renamed, nested objects are left as they are. Should two
+    /// keys be renamed to the same one, the value of the one that comes last
+    /// wins. See `json::snake_to_camel_case` and `json::camel_to_snake_case`
+    /// for common conversions.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = object!{ user_id: 1, display_name: "foo" };
+    ///
+    /// data.rename_keys_with(json::snake_to_camel_case).unwrap();
+    ///
+    /// assert_eq!(data, object!{ userId: 1, displayName: "foo" });
+    /// # }
+    /// ```
+    pub fn rename_keys_with<F>(&mut self, f: F) -> Result<()>
+    where F: Fn(&str) -> String {
+        match *self {
+            JsonValue::Object(ref mut object) => {
+                let mut renamed = Object::with_capacity(object.len());
+
+                for (key, value) in object.iter_mut() {
+                    renamed.insert(&f(key), value.take());
+                }
+
+                *object = renamed;
+                Ok(())
+            },
+            _ => Err(Error::wrong_type("Object"))
+        }
+    }
+
+    /// Works on `JsonValue::Object` - recursively merge another object into
+    /// this one, such as when layering configuration files. Keys missing
+    /// from this object are added, objects present on both sides are merged
+    /// key by key, and arrays on both sides are combined according to
+    /// `array_strategy`. Any other value is replaced by the one from `other`.
+    ///
+    /// Errors if either value is not an object, leaving this one untouched.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// use json::ArrayMergeStrategy;
+    ///
+    /// let mut config = object!{ name: "app", plugins: ["a"], db: { host: "localhost", port: 5432 } };
+    ///
+    /// config.merge_with(object!{ plugins: ["b"], db: { port: 6543 } }, ArrayMergeStrategy::Concat).unwrap();
+    ///
+    /// assert_eq!(config, object!{ name: "app", plugins: ["a", "b"], db: { host: "localhost", port: 6543 } });
+    /// # }
+    /// ```
+    pub fn merge_with(&mut self, other: JsonValue, array_strategy: ArrayMergeStrategy) -> Result<()> {
+        if !self.is_object() || !other.is_object() {
+            return Err(Error::wrong_type("Object"));
+        }
+
+        merge_values(self, other, array_strategy);
+
+        Ok(())
+    }
+
+    /// Works on `JsonValue::Object` - recursively fill in keys that are
+    /// missing from this object with clones of the values in `defaults`.
+    /// Objects present on both sides are filled in key by key, any other
+    /// value already present is kept as it is, including an explicit `null`
+    /// and arrays, which are never merged.
+    ///
+    /// Errors if either value is not an object, leaving this one untouched.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut config = object!{ db: { host: "example.com" } };
+    ///
+    /// config.apply_defaults(&object!{ debug: false, db: { host: "localhost", port: 5432 } }).unwrap();
+    ///
+    /// assert_eq!(config, object!{ db: { host: "example.com", port: 5432 }, debug: false });
+    /// # }
+    /// ```
+    pub fn apply_defaults(&mut self, defaults: &JsonValue) -> Result<()> {
+        match (self, defaults) {
+            (JsonValue::Object(object), JsonValue::Object(defaults)) => {
+                for (key, default) in defaults.iter() {
+                    match object.get_mut(key) {
+                        Some(value) => {
+                            if value.is_object() && default.is_object() {
+                                value.apply_defaults(default)?;
+                            }
+                        },
+                        None => object.insert(key, default.clone()),
+                    }
+                }
+
+                Ok(())
+            },
+            _ => Err(Error::wrong_type("Object"))
+        }
+    }
+
+    /// Recursively remove all object keys that hold `JsonValue::Null`, for
+    /// when an absent key and a null mean the same thing. With
+    /// `drop_array_nulls` set, `Null` members are removed from arrays as well,
+    /// otherwise they are kept so that indexes don't shift. Objects and arrays
+    /// that end up empty are kept. Any other value is left untouched.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = object!{ a: null, b: [1, null], c: { d: null } };
+    ///
+    /// data.compact(false);
+    ///
+    /// assert_eq!(data, object!{ b: [1, null], c: {} });
+    /// # }
+    /// ```
+    pub fn compact(&mut self, drop_array_nulls: bool) {
+        match *self {
+            JsonValue::Object(ref mut object) => {
+                let mut compacted = Object::with_capacity(object.len());
+
+                for (key, value) in object.iter_mut() {
+                    value.compact(drop_array_nulls);
+
+                    if !value.is_null() {
+                        compacted.insert(key, value.take());
+                    }
+                }
+
+                *object = compacted;
+            },
+            JsonValue::Array(ref mut vec) => {
+                for member in vec.iter_mut() {
+                    member.compact(drop_array_nulls);
+                }
+
+                if drop_array_nulls {
+                    vec.retain(|member| !member.is_null());
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Recursively rewrite all numbers into a canonical form, so that numbers
+    /// which are equal also serialize the same way: `-0.0` and `-0` become
+    /// `0`, and trailing zeroes after the decimal point are stripped, turning
+    /// `1.0` into `1` and `1.50` into `1.5`. NaN, as well as raw numbers from
+    /// `json::parse_preserve_numbers`, are left untouched.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = json::parse("[-0.0, 1.0, 2.50]").unwrap();
+    ///
+    /// data.normalize_numbers();
+    ///
+    /// assert_eq!(data.dump(), "[0,1,2.5]");
+    /// # }
+    /// ```
+    pub fn normalize_numbers(&mut self) {
+        match *self {
+            JsonValue::Number(ref mut number) => {
+                if number.is_zero() {
+                    *number = Number::from(0);
+                } else if !number.is_nan() {
+                    let (positive, mantissa, exponent) = number.as_parts();
+
+                    *number = Number::from_parts(positive, mantissa, exponent);
+                }
+            },
+            JsonValue::Object(ref mut object) => {
+                for (_, value) in object.iter_mut() {
+                    value.normalize_numbers();
+                }
+            },
+            JsonValue::Array(ref mut vec) => {
+                for member in vec.iter_mut() {
+                    member.normalize_numbers();
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Recursively convert every string holding a valid JSON number, such
+    /// as `"42"` or `"-1.5e3"`, into a number. The whole string has to be the
+    /// number, so `" 42"`, `"42px"` or `"+42"` are left untouched, same as
+    /// all other strings. Object keys are never converted.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = object!{ id: "42", price: ["3.14", "n/a"] };
+    ///
+    /// data.coerce_numeric_strings();
+    ///
+    /// assert_eq!(data, object!{ id: 42, price: [3.14, "n/a"] });
+    /// # }
+    /// ```
+    pub fn coerce_numeric_strings(&mut self) {
+        match *self {
+            JsonValue::Short(_) | JsonValue::String(_) => {
+                let number = match self.as_str() {
+                    // Rule out surrounding whitespace, which `parse` would skip
+                    Some(s) if s.starts_with(|ch: char| ch == '-' || ch.is_ascii_digit())
+                            && s.ends_with(|ch: char| ch.is_ascii_digit()) => crate::parse(s),
+                    _ => return,
+                };
+
+                if let Ok(number @ JsonValue::Number(_)) = number {
+                    *self = number;
+                }
+            },
+            JsonValue::Object(ref mut object) => {
+                for (_, value) in object.iter_mut() {
+                    value.coerce_numeric_strings();
+                }
+            },
+            JsonValue::Array(ref mut vec) => {
+                for member in vec.iter_mut() {
+                    member.coerce_numeric_strings();
+                }
+            },
+            _ => {}
+        }
+    }
+
+    /// Recursively reorder the keys of every object, including objects nested
+    /// in arrays, into the same order `dump_sorted_keys` writes them in, so
+    /// that the regular `dump` and `pretty` produce canonical output. Arrays
+    /// keep their order.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = object!{ b: [{ z: 1, y: 2 }], a: { d: 3, c: 4 } };
+    ///
+    /// data.sort_keys_recursive();
+    ///
+    /// assert_eq!(data.dump(), r#"{"a":{"c":4,"d":3},"b":[{"y":2,"z":1}]}"#);
+    /// # }
+    /// ```
+    pub fn sort_keys_recursive(&mut self) {
+        match *self {
+            JsonValue::Object(ref mut object) => {
+                let mut sorted = Object::with_capacity(object.len());
+                let mut entries: Vec<_> = object.iter_mut().collect();
+
+                sort_entries(&mut entries);
+
+                for (key, value) in entries {
+                    value.sort_keys_recursive();
+                    sorted.insert(key, value.take());
+                }
+
+                *object = sorted;
             },
-            _ => JsonValue::Null
+            JsonValue::Array(ref mut vec) => {
+                for member in vec.iter_mut() {
+                    member.sort_keys_recursive();
+                }
+            },
+            _ => {}
         }
     }
 
@@ -555,6 +2492,409 @@ impl JsonValue {
         }
     }
 
+    /// Normalize a "one or many" value into an array. Arrays are returned
+    /// unchanged, `Null` becomes an empty array, and any other value is
+    /// wrapped in a single element array.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// assert_eq!(json::from("foo").as_array_lenient(), array!["foo"]);
+    /// assert_eq!(array![1, 2].as_array_lenient(), array![1, 2]);
+    /// assert_eq!(json::Null.as_array_lenient(), array![]);
+    /// # }
+    /// ```
+    pub fn as_array_lenient(&self) -> JsonValue {
+        match *self {
+            JsonValue::Array(_) => self.clone(),
+            JsonValue::Null     => JsonValue::new_array(),
+            _                   => JsonValue::Array(vec![self.clone()]),
+        }
+    }
+
+    /// Works on `JsonValue::Array` - get a reference to the first member.
+    /// Errors with `Error::ArrayIndexOutOfBounds` if the array is empty, and
+    /// with `Error::WrongType` if called on anything but an array.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = array!["foo", "bar", "baz"];
+    ///
+    /// assert_eq!(data.first().unwrap(), "foo");
+    /// assert_eq!(data.last().unwrap(), "baz");
+    /// # }
+    /// ```
+    pub fn first(&self) -> Result<&JsonValue> {
+        match *self {
+            JsonValue::Array(ref vec) => {
+                vec.first().ok_or(Error::ArrayIndexOutOfBounds { index: 0, len: 0 })
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - get a reference to the last member.
+    /// See `first`.
+    pub fn last(&self) -> Result<&JsonValue> {
+        match *self {
+            JsonValue::Array(ref vec) => {
+                vec.last().ok_or(Error::ArrayIndexOutOfBounds { index: 0, len: 0 })
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - destructure an array of exactly two
+    /// members, such as a `[lat, lng]` pair. Errors if called on anything but
+    /// an array, or if the array is of any other length.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = array![52.52, 13.40];
+    ///
+    /// let (lat, lng) = data.as_pair().unwrap();
+    ///
+    /// assert_eq!(lat.as_f64(), Some(52.52));
+    /// assert_eq!(lng.as_f64(), Some(13.40));
+    /// # }
+    /// ```
+    pub fn as_pair(&self) -> Result<(&JsonValue, &JsonValue)> {
+        match *self {
+            JsonValue::Array(ref vec) => match vec.as_slice() {
+                [a, b] => Ok((a, b)),
+                _      => Err(Error::wrong_type("Array of length 2")),
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - destructure an array of exactly three
+    /// members. Errors if called on anything but an array, or if the array is
+    /// of any other length.
+    pub fn as_triple(&self) -> Result<(&JsonValue, &JsonValue, &JsonValue)> {
+        match *self {
+            JsonValue::Array(ref vec) => match vec.as_slice() {
+                [a, b, c] => Ok((a, b, c)),
+                _         => Err(Error::wrong_type("Array of length 3")),
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - split the members into an array of
+    /// arrays, each of length `size`, except for the last one which may be
+    /// shorter. Errors if called on anything but an array, or if `size` is 0.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = array![1, 2, 3, 4, 5];
+    ///
+    /// assert_eq!(data.chunks(2).unwrap(), array![[1, 2], [3, 4], [5]]);
+    /// # }
+    /// ```
+    pub fn chunks(&self, size: usize) -> Result<JsonValue> {
+        match *self {
+            JsonValue::Array(ref vec) => {
+                if size == 0 {
+                    return Err(Error::invalid_argument("Chunk size must be greater than 0"));
+                }
+
+                Ok(JsonValue::Array(vec.chunks(size).map(|chunk| JsonValue::Array(chunk.to_vec())).collect()))
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - collect every run of `size` consecutive
+    /// members into an array of overlapping windows, sliding by one member at
+    /// a time. If `size` is larger than the array, there are no windows and
+    /// the result is empty. Errors if called on anything but an array, or if
+    /// `size` is 0.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = array![1, 2, 3, 4];
+    ///
+    /// assert_eq!(data.windows(3).unwrap(), array![[1, 2, 3], [2, 3, 4]]);
+    /// # }
+    /// ```
+    pub fn windows(&self, size: usize) -> Result<JsonValue> {
+        match *self {
+            JsonValue::Array(ref vec) => {
+                if size == 0 {
+                    return Err(Error::invalid_argument("Window size must be greater than 0"));
+                }
+
+                Ok(JsonValue::Array(vec.windows(size).map(|window| JsonValue::Array(window.to_vec())).collect()))
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - split the members into two new arrays,
+    /// the first with all members for which `f` returns `true`, the second
+    /// with the rest, both keeping the original order. Errors if called on
+    /// anything but an array.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = array![1, 2, 3, 4, 5];
+    ///
+    /// let (even, odd) = data.partition(|n| n.as_u32().unwrap() % 2 == 0).unwrap();
+    ///
+    /// assert_eq!(even, array![2, 4]);
+    /// assert_eq!(odd, array![1, 3, 5]);
+    /// # }
+    /// ```
+    pub fn partition<F>(&self, mut f: F) -> Result<(JsonValue, JsonValue)>
+    where F: FnMut(&JsonValue) -> bool {
+        match *self {
+            JsonValue::Array(ref vec) => {
+                let (matching, rest) = vec.iter().cloned().partition(|member| f(member));
+
+                Ok((JsonValue::Array(matching), JsonValue::Array(rest)))
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - build a new array by calling `f` on
+    /// every member. When `f` returns an array, its members are spliced into
+    /// the result one by one, so returning an empty array drops the member,
+    /// any other value is added as it is. Only a single level is spliced, so
+    /// to add an array as a single member, wrap it in another array. Errors
+    /// if called on anything but an array.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = array![1, 2, 3];
+    ///
+    /// let doubled = data.flat_map_array(|n| array![n.clone(), n.clone()]).unwrap();
+    ///
+    /// assert_eq!(doubled, array![1, 1, 2, 2, 3, 3]);
+    /// # }
+    /// ```
+    pub fn flat_map_array<F>(&self, mut f: F) -> Result<JsonValue>
+    where F: FnMut(&JsonValue) -> JsonValue {
+        match *self {
+            JsonValue::Array(ref vec) => {
+                let mut mapped = Vec::with_capacity(vec.len());
+
+                for member in vec {
+                    match f(member) {
+                        JsonValue::Array(members) => mapped.extend(members),
+                        value => mapped.push(value),
+                    }
+                }
+
+                Ok(JsonValue::Array(mapped))
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - swap two members in place. Errors with
+    /// `Error::ArrayIndexOutOfBounds` if either index is out of bounds, and
+    /// with `Error::WrongType` if called on anything but an array.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = array![1, 2, 3];
+    ///
+    /// data.swap(0, 2).unwrap();
+    ///
+    /// assert_eq!(data, array![3, 2, 1]);
+    /// # }
+    /// ```
+    pub fn swap(&mut self, i: usize, j: usize) -> Result<()> {
+        match *self {
+            JsonValue::Array(ref mut vec) => {
+                let len = vec.len();
+
+                if let Some(index) = [i, j].iter().cloned().find(|&index| index >= len) {
+                    return Err(Error::ArrayIndexOutOfBounds { index, len });
+                }
+
+                vec.swap(i, j);
+                Ok(())
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - rotate the array in place so that the
+    /// member at index `n` becomes the first one. Unlike the slice method,
+    /// `n` is taken modulo the length of the array, so it never panics.
+    /// Errors with `Error::WrongType` if called on anything but an array.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = array![1, 2, 3, 4, 5];
+    ///
+    /// data.rotate_left(2).unwrap();
+    /// assert_eq!(data, array![3, 4, 5, 1, 2]);
+    ///
+    /// data.rotate_right(7).unwrap();
+    /// assert_eq!(data, array![1, 2, 3, 4, 5]);
+    /// # }
+    /// ```
+    pub fn rotate_left(&mut self, n: usize) -> Result<()> {
+        match *self {
+            JsonValue::Array(ref mut vec) => {
+                if !vec.is_empty() {
+                    let n = n % vec.len();
+                    vec.rotate_left(n);
+                }
+
+                Ok(())
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - rotate the array in place so that the
+    /// last `n` members become the first ones. Same as `rotate_left`, `n` is
+    /// taken modulo the length of the array.
+    pub fn rotate_right(&mut self, n: usize) -> Result<()> {
+        match *self {
+            JsonValue::Array(ref mut vec) => {
+                if !vec.is_empty() {
+                    let n = n % vec.len();
+                    vec.rotate_right(n);
+                }
+
+                Ok(())
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - remove a member and return it, replacing
+    /// it with the last member of the array. This does not preserve order,
+    /// but is O(1). Errors same as `swap`.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = array!["a", "b", "c", "d"];
+    ///
+    /// assert_eq!(data.swap_remove(1).unwrap(), "b");
+    /// assert_eq!(data, array!["a", "d", "c"]);
+    /// # }
+    /// ```
+    pub fn swap_remove(&mut self, index: usize) -> Result<JsonValue> {
+        match *self {
+            JsonValue::Array(ref mut vec) => {
+                if index >= vec.len() {
+                    return Err(Error::ArrayIndexOutOfBounds { index, len: vec.len() });
+                }
+
+                Ok(vec.swap_remove(index))
+            },
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - binary search a sorted array for
+    /// `target`, ordered as per `JsonValue::total_cmp`. The inner
+    /// result is `Ok` with the index of a matching member, or `Err` with
+    /// the index at which `target` could be inserted to keep the array
+    /// sorted, same as `slice::binary_search`. If the array isn't sorted
+    /// the result is unspecified. Errors with `Error::WrongType` if called
+    /// on anything but an array.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = array![1, 3, 5];
+    ///
+    /// assert_eq!(data.binary_search(&3.into()).unwrap(), Ok(1));
+    /// assert_eq!(data.binary_search(&4.into()).unwrap(), Err(2));
+    /// # }
+    /// ```
+    pub fn binary_search(&self, target: &JsonValue) -> Result<std::result::Result<usize, usize>> {
+        match *self {
+            JsonValue::Array(ref vec) => Ok(vec.binary_search_by(|probe| probe.total_cmp(target))),
+            _ => Err(Error::wrong_type("Array"))
+        }
+    }
+
+    /// Works on `JsonValue::Array` - sum of all members. An empty array sums
+    /// up to `0.0`. Errors if any member is not a number, mentioning its
+    /// index, or if called on anything but an array.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = array![1, 2, 3, 4];
+    ///
+    /// assert_eq!(data.sum().unwrap(), 10.0);
+    /// assert_eq!(data.avg().unwrap(), 2.5);
+    /// assert_eq!(data.min_number().unwrap(), 1.0);
+    /// assert_eq!(data.max_number().unwrap(), 4.0);
+    /// # }
+    /// ```
+    pub fn sum(&self) -> Result<f64> {
+        Ok(self.fold_numbers(|a, b| a + b)?.unwrap_or(0.0))
+    }
+
+    /// Works on `JsonValue::Array` - arithmetic mean of all members. Errors
+    /// with `Error::EmptyArray` on an empty array, otherwise same as `sum`.
+    pub fn avg(&self) -> Result<f64> {
+        let sum = self.fold_numbers(|a, b| a + b)?;
+
+        match sum {
+            Some(sum) => Ok(sum / self.len() as f64),
+            None      => Err(Error::EmptyArray),
+        }
+    }
+
+    /// Works on `JsonValue::Array` - smallest of all members. Errors same
+    /// as `avg`. Not to be confused with `Ord::min`, which compares two
+    /// values against each other.
+    pub fn min_number(&self) -> Result<f64> {
+        self.fold_numbers(f64::min)?.ok_or(Error::EmptyArray)
+    }
+
+    /// Works on `JsonValue::Array` - largest of all members. Errors same
+    /// as `avg`.
+    pub fn max_number(&self) -> Result<f64> {
+        self.fold_numbers(f64::max)?.ok_or(Error::EmptyArray)
+    }
+
+    // Fold all members of an array as `f64`, `None` if the array is empty.
+    fn fold_numbers<F>(&self, f: F) -> Result<Option<f64>>
+    where F: Fn(f64, f64) -> f64 {
+        let vec = match *self {
+            JsonValue::Array(ref vec) => vec,
+            _ => return Err(Error::wrong_type("Array")),
+        };
+
+        let mut result = None;
+
+        for (index, member) in vec.iter().enumerate() {
+            let number = match member.as_f64() {
+                Some(number) => number,
+                None => return Err(Error::wrong_type(&format!("Number at index {}", index))),
+            };
+
+            result = Some(match result {
+                Some(result) => f(result, number),
+                None         => number,
+            });
+        }
+
+        Ok(result)
+    }
+
     /// When called on an array or an object, will wipe them clean. When called
     /// on a string will clear the string. Numbers and booleans become null.
     pub fn clear(&mut self) {
@@ -565,6 +2905,61 @@ impl JsonValue {
             _                                 => *self = JsonValue::Null,
         }
     }
+
+    /// Estimate the number of bytes used by this value, including everything
+    /// it holds on the heap: capacities of strings, arrays and objects, and
+    /// object keys that are too long to be stored inline. Every heap
+    /// allocation is counted as its size rounded up to the next power of two,
+    /// plus 16 bytes of allocator bookkeeping, so with common allocators this
+    /// is an upper bound of the actual memory use, which makes it suitable for
+    /// enforcing memory budgets.
+    ///
+    /// ```
+    /// # use json::JsonValue;
+    /// let small = JsonValue::from("foo");
+    /// let large = JsonValue::from("foo".repeat(100));
+    ///
+    /// assert_eq!(small.estimated_size(), std::mem::size_of::<JsonValue>());
+    /// assert_eq!(large.estimated_size(), std::mem::size_of::<JsonValue>() + 512 + 16);
+    /// ```
+    pub fn estimated_size(&self) -> usize {
+        mem::size_of::<JsonValue>() + self.heap_size()
+    }
+
+    pub(crate) fn heap_size(&self) -> usize {
+        match *self {
+            JsonValue::String(ref string) => allocation_size(string.capacity()),
+            JsonValue::Array(ref vec) => {
+                allocation_size(vec.capacity() * mem::size_of::<JsonValue>()) +
+                vec.iter().map(JsonValue::heap_size).sum::<usize>()
+            },
+            JsonValue::Object(ref object) => object.heap_size(),
+            _ => 0,
+        }
+    }
+
+    /// Recursively shrink the capacity of all strings, arrays and objects
+    /// contained within, reclaiming memory left over after heavy mutation.
+    pub fn shrink_to_fit(&mut self) {
+        match *self {
+            JsonValue::String(ref mut string) => string.shrink_to_fit(),
+            JsonValue::Array(ref mut vec) => {
+                vec.shrink_to_fit();
+
+                for member in vec.iter_mut() {
+                    member.shrink_to_fit();
+                }
+            },
+            JsonValue::Object(ref mut object) => {
+                object.shrink_to_fit();
+
+                for (_, value) in object.iter_mut() {
+                    value.shrink_to_fit();
+                }
+            },
+            _ => {}
+        }
+    }
 }
 
 /// Implements indexing by `usize` to easily access array members:
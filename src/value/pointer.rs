@@ -0,0 +1,281 @@
+// This is a private module that contains JSON Pointer (RFC 6901) support
+// for `JsonValue`.
+
+use std::borrow::Cow;
+use std::mem;
+
+use crate::{ Result, Error };
+use crate::value::JsonValue;
+
+impl JsonValue {
+    /// Look up a value by a JSON Pointer, such as `/foo/0/bar`. An empty
+    /// pointer refers to the whole value. Returns `None` if the pointer is
+    /// malformed or if any step along the path can't be resolved.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ foo: [{ bar: 42 }], "a/b": true };
+    ///
+    /// assert_eq!(data.pointer("/foo/0/bar"), Some(&json::from(42)));
+    /// assert_eq!(data.pointer("/a~1b"), Some(&json::from(true)));
+    /// assert_eq!(data.pointer("/foo/1"), None);
+    /// assert_eq!(data.pointer(""), Some(&data));
+    /// # }
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonValue> {
+        let mut target = self;
+
+        for token in tokens(pointer)? {
+            let token = unescape_token(token);
+
+            target = match *target {
+                JsonValue::Object(ref object) => object.get(&token)?,
+                JsonValue::Array(ref vec)     => vec.get(parse_index(&token)?)?,
+                _                             => return None,
+            };
+        }
+
+        Some(target)
+    }
+
+    /// Mutable version of `pointer`.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut JsonValue> {
+        let mut target = self;
+
+        for token in tokens(pointer)? {
+            let token = unescape_token(token);
+
+            target = match *target {
+                JsonValue::Object(ref mut object) => object.get_mut(&token)?,
+                JsonValue::Array(ref mut vec)     => vec.get_mut(parse_index(&token)?)?,
+                _                                 => return None,
+            };
+        }
+
+        Some(target)
+    }
+
+    /// Replace the value behind a JSON Pointer, returning the previous value.
+    /// An empty pointer replaces the whole value. Errors with
+    /// `Error::UndefinedField` carrying the pointer if it can't be resolved.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = object!{ foo: { bar: [1, 2] } };
+    ///
+    /// assert_eq!(data.replace_at("/foo/bar/1", "two").unwrap(), 2);
+    /// assert_eq!(data, object!{ foo: { bar: [1, "two"] } });
+    /// # }
+    /// ```
+    pub fn replace_at<T>(&mut self, pointer: &str, value: T) -> Result<JsonValue>
+    where T: Into<JsonValue> {
+        match self.pointer_mut(pointer) {
+            Some(target) => Ok(mem::replace(target, value.into())),
+            None         => Err(Error::undefined(pointer)),
+        }
+    }
+
+    /// Replace every value behind one of the JSON `pointers` with the `mask`
+    /// string, leaving the rest of the structure intact. Pointers that can't
+    /// be resolved are skipped. Errors with `Error::InvalidArgument` if any of
+    /// the pointers is malformed, in which case nothing is masked.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let mut data = object!{ user: { name: "Jane", ssn: "123-45-6789" } };
+    ///
+    /// data.redact(&["/user/ssn", "/user/card"], "***").unwrap();
+    ///
+    /// assert_eq!(data, object!{ user: { name: "Jane", ssn: "***" } });
+    /// # }
+    /// ```
+    pub fn redact(&mut self, pointers: &[&str], mask: &str) -> Result<()> {
+        if let Some(pointer) = pointers.iter().find(|pointer| tokens(pointer).is_none()) {
+            return Err(Error::invalid_argument(&format!("Expected JSON Pointer, got {:?}", pointer)));
+        }
+
+        for pointer in pointers {
+            if let Some(target) = self.pointer_mut(pointer) {
+                *target = mask.into();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Walk the whole value, collecting the JSON Pointer of every leaf along
+    /// with a reference to it, in order. Leaves are all values that aren't
+    /// objects or arrays, as well as empty objects and arrays, so that no
+    /// part of the structure is lost when flattening. A value that isn't an
+    /// object or an array is itself the only leaf, with an empty pointer.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ a: { b: 1 }, c: [true, []] };
+    ///
+    /// let pointers: Vec<String> = data.leaves().into_iter().map(|(pointer, _)| pointer).collect();
+    ///
+    /// assert_eq!(pointers, ["/a/b", "/c/0", "/c/1"]);
+    /// # }
+    /// ```
+    pub fn leaves(&self) -> Vec<(String, &JsonValue)> {
+        let mut leaves = Vec::new();
+
+        self.collect_leaves(&mut String::new(), &mut leaves);
+
+        leaves
+    }
+
+    /// Rebuild a nested value out of JSON Pointer and leaf value pairs, such
+    /// as those produced by `leaves`. Intermediate objects and arrays are
+    /// created as needed, a numeric token creates an array, any other token
+    /// creates an object. Array members have to appear in order, a token can
+    /// refer to an existing member or append a new one right at the end.
+    /// Should the same pointer appear twice, the last value wins.
+    ///
+    /// Errors with `Error::ArrayIndexOutOfBounds` if a token skips past the
+    /// end of an array, so that an index like `/a/4000000000` can't allocate
+    /// an arbitrary amount of memory, or with `Error::InvalidArgument` if a
+    /// pointer is malformed. Also errors if a pointer conflicts with a
+    /// previous pair, such as using a non-numeric token on an array, or
+    /// descending into a value that is neither an object nor an array.
+    ///
+    /// **Note:** numeric object keys, such as `{"0": true}`, will turn into
+    /// arrays, so those won't survive a round trip through `leaves`.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # use json::JsonValue;
+    /// # fn main() {
+    /// let data = JsonValue::unflatten(vec![
+    ///     ("/a/b".to_string(), json::from(1)),
+    ///     ("/c/0".to_string(), json::from(true)),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(data, object!{ a: { b: 1 }, c: [true] });
+    /// # }
+    /// ```
+    pub fn unflatten<I>(pairs: I) -> Result<JsonValue>
+    where I: IntoIterator<Item = (String, JsonValue)> {
+        let mut root = JsonValue::Null;
+
+        for (pointer, value) in pairs {
+            let tokens = match tokens(&pointer) {
+                Some(tokens) => tokens,
+                None         => return Err(Error::invalid_argument(&format!("Expected JSON Pointer, got {:?}", pointer))),
+            };
+
+            let mut target = &mut root;
+            let mut path = 0;
+
+            for raw in tokens {
+                let token = unescape_token(raw);
+                let index = parse_index(&token);
+
+                if target.is_null() {
+                    *target = match index {
+                        Some(_) => JsonValue::new_array(),
+                        None    => JsonValue::new_object(),
+                    };
+                }
+
+                target = match *target {
+                    JsonValue::Object(ref mut object) => &mut object[&*token],
+                    JsonValue::Array(ref mut vec) => match index {
+                        Some(index) => {
+                            if index > vec.len() {
+                                return Err(Error::ArrayIndexOutOfBounds { index, len: vec.len() });
+                            }
+
+                            if index == vec.len() {
+                                vec.push(JsonValue::Null);
+                            }
+
+                            &mut vec[index]
+                        },
+                        None => return Err(Error::wrong_type(&format!("Object at {:?}", &pointer[..path]))),
+                    },
+                    _ => return Err(Error::wrong_type(&format!("Object or Array at {:?}", &pointer[..path]))),
+                };
+
+                path += raw.len() + 1;
+            }
+
+            *target = value;
+        }
+
+        Ok(root)
+    }
+
+    fn collect_leaves<'a>(&'a self, pointer: &mut String, leaves: &mut Vec<(String, &'a JsonValue)>) {
+        let len = pointer.len();
+
+        match *self {
+            JsonValue::Object(ref object) if !object.is_empty() => {
+                for (key, value) in object.iter() {
+                    pointer.push('/');
+                    escape_token(pointer, key);
+                    value.collect_leaves(pointer, leaves);
+                    pointer.truncate(len);
+                }
+            },
+            JsonValue::Array(ref vec) if !vec.is_empty() => {
+                for (index, value) in vec.iter().enumerate() {
+                    pointer.push('/');
+                    pointer.push_str(&index.to_string());
+                    value.collect_leaves(pointer, leaves);
+                    pointer.truncate(len);
+                }
+            },
+            _ => leaves.push((pointer.clone(), self)),
+        }
+    }
+}
+
+// Append a key to a pointer, escaping `~` and `/` as per RFC 6901.
+pub(crate) fn escape_token(pointer: &mut String, key: &str) {
+    for ch in key.chars() {
+        match ch {
+            '~' => pointer.push_str("~0"),
+            '/' => pointer.push_str("~1"),
+            _   => pointer.push(ch),
+        }
+    }
+}
+
+// Split a pointer into its raw reference tokens, `None` if it's malformed.
+pub(crate) fn tokens(pointer: &str) -> Option<impl Iterator<Item = &str>> {
+    let mut tokens = pointer.split('/');
+
+    // Every token is prefixed with a `/`, so the first split is always
+    // empty, for an empty pointer it's also the last
+    match tokens.next() {
+        Some("") => Some(tokens),
+        _        => None,
+    }
+}
+
+pub(crate) fn unescape_token(token: &str) -> Cow<'_, str> {
+    if token.contains('~') {
+        Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+// Array indexes are plain decimals, leading zeros are not allowed.
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+
+    if !token.bytes().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+
+    token.parse().ok()
+}
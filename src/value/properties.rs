@@ -0,0 +1,140 @@
+// This is a private module that contains conversions between `JsonValue`
+// and flat `.properties` style text.
+
+use crate::{ Result, Error };
+use crate::value::JsonValue;
+use crate::value::pointer::{ escape_token, unescape_token };
+
+impl JsonValue {
+    /// Flatten an object or an array into `.properties` style lines of
+    /// `key=value`, where nested keys are joined with dots, and array members
+    /// use their index, such as `servers.0.host=localhost`. Lines are written
+    /// in document order, each terminated with a `\n`.
+    ///
+    /// All values are written out as plain strings, with `null` becoming an
+    /// empty string, so their types are lost. Empty objects and arrays are
+    /// skipped. Backslashes, line feeds and carriage returns in values are
+    /// escaped as `\\`, `\n` and `\r` respectively.
+    ///
+    /// Errors with `Error::WrongType` if called on anything but an object or
+    /// an array, or with `Error::InvalidArgument` if any key contains a `.`,
+    /// a `=` or a line break, since those would be impossible to read back.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ db: { host: "localhost", ports: [5432, 5433] } };
+    ///
+    /// assert_eq!(data.to_properties().unwrap(), "db.host=localhost\ndb.ports.0=5432\ndb.ports.1=5433\n");
+    /// # }
+    /// ```
+    pub fn to_properties(&self) -> Result<String> {
+        match *self {
+            JsonValue::Object(_) | JsonValue::Array(_) => {},
+            _ => return Err(Error::wrong_type("Object or Array")),
+        }
+
+        let mut text = String::new();
+
+        for (pointer, value) in self.leaves() {
+            let value = match *value {
+                JsonValue::Object(_) | JsonValue::Array(_) => continue,
+                _ => value.to_display_string(),
+            };
+
+            for (index, token) in pointer[1..].split('/').enumerate() {
+                let key = unescape_token(token);
+
+                if key.contains(&['.', '=', '\n', '\r'][..]) {
+                    return Err(Error::invalid_argument(&format!("Expected key without `.`, `=` or line breaks, got {:?}", key)));
+                }
+
+                if index != 0 {
+                    text.push('.');
+                }
+
+                text.push_str(&key);
+            }
+
+            text.push('=');
+
+            for ch in value.chars() {
+                match ch {
+                    '\\' => text.push_str("\\\\"),
+                    '\n' => text.push_str("\\n"),
+                    '\r' => text.push_str("\\r"),
+                    _    => text.push(ch),
+                }
+            }
+
+            text.push('\n');
+        }
+
+        Ok(text)
+    }
+
+    /// Rebuild a nested value from `.properties` style text, as produced by
+    /// `to_properties`. Every value is read as a string. Keys are split on
+    /// dots, and numeric segments create arrays, see `unflatten` for details.
+    /// Empty lines, as well as lines starting with `#` or `!`, are ignored.
+    ///
+    /// Errors with `Error::InvalidSyntax` carrying the line number if a line
+    /// has no `=`. Also errors if keys conflict with each other, or if an
+    /// array index skips past the end of the array, as `unflatten` does.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # use json::JsonValue;
+    /// # fn main() {
+    /// let data = JsonValue::from_properties("# config\ndb.host=localhost\ndb.ports.0=5432\n").unwrap();
+    ///
+    /// assert_eq!(data, object!{ db: { host: "localhost", ports: ["5432"] } });
+    /// # }
+    /// ```
+    pub fn from_properties(text: &str) -> Result<JsonValue> {
+        let mut pairs = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+                continue;
+            }
+
+            let (key, value) = match line.find('=') {
+                Some(split) => (&line[..split], &line[split + 1..]),
+                None => return Err(Error::InvalidSyntax { line: index + 1, expected: "key=value".into() }),
+            };
+
+            let mut pointer = String::with_capacity(key.len() + 1);
+
+            for segment in key.split('.') {
+                pointer.push('/');
+                escape_token(&mut pointer, segment);
+            }
+
+            pairs.push((pointer, JsonValue::from(unescape_value(value))));
+        }
+
+        JsonValue::unflatten(pairs)
+    }
+}
+
+fn unescape_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            unescaped.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n')  => unescaped.push('\n'),
+            Some('r')  => unescaped.push('\r'),
+            Some(ch)   => unescaped.push(ch),
+            None       => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
@@ -0,0 +1,279 @@
+// This is a private module that contains conversions between `JsonValue`
+// and URL query strings.
+
+use std::fmt::Write;
+
+use crate::{ Result, Error };
+use crate::value::JsonValue;
+
+impl JsonValue {
+    /// Parse a URL query string, such as `a=1&b=two&b=three`, into a
+    /// `JsonValue::Object`. Keys and values are percent-decoded (with `+`
+    /// standing in for a space) and every value is kept as a string, no
+    /// type inference is performed. Keys that appear more than once are
+    /// collected into an array, in order of appearance.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # use json::JsonValue;
+    /// # fn main() {
+    /// let data = JsonValue::from_query_string("a=1&b=two&b=three");
+    ///
+    /// assert_eq!(data, object!{
+    ///     a: "1",
+    ///     b: ["two", "three"]
+    /// });
+    /// # }
+    /// ```
+    pub fn from_query_string(qs: &str) -> JsonValue {
+        let qs = qs.strip_prefix('?').unwrap_or(qs);
+        let mut data = JsonValue::new_object();
+
+        for pair in qs.split('&') {
+            if pair.is_empty() {
+                continue;
+            }
+
+            let (key, value) = match pair.find('=') {
+                Some(index) => (&pair[..index], &pair[index + 1..]),
+                None        => (pair, ""),
+            };
+
+            let value = JsonValue::from(percent_decode(value).as_str());
+            let entry = &mut data[percent_decode(key).as_str()];
+
+            match *entry {
+                JsonValue::Null               => *entry = value,
+                JsonValue::Array(ref mut vec) => vec.push(value),
+                _ => {
+                    let first = entry.take();
+                    *entry = JsonValue::Array(vec![first, value]);
+                }
+            }
+        }
+
+        data
+    }
+
+    /// Build nested objects and arrays out of already decoded form fields
+    /// using bracket notation, such as `user[name]` or `user[roles][]`.
+    /// Every bracketed segment descends into an object under that key, while
+    /// an empty `[]` appends a new member to an array. Values are always kept
+    /// as strings, and if the same key is set more than once, the last value
+    /// wins.
+    ///
+    /// Errors with `Error::InvalidArgument` if a key is malformed, such as
+    /// `a[b` or `[a]`, or if a key would need a value to be both an object
+    /// and an array, or descend into a string.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # use json::JsonValue;
+    /// # fn main() {
+    /// let data = JsonValue::from_bracketed_form(&[
+    ///     ("user[name]", "Jane"),
+    ///     ("user[roles][]", "admin"),
+    ///     ("user[roles][]", "dev"),
+    ///     ("page", "2"),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(data, object!{
+    ///     user: { name: "Jane", roles: ["admin", "dev"] },
+    ///     page: "2"
+    /// });
+    /// # }
+    /// ```
+    pub fn from_bracketed_form(pairs: &[(&str, &str)]) -> Result<JsonValue> {
+        let mut data = JsonValue::new_object();
+
+        for &(key, value) in pairs {
+            let segments = bracket_segments(key).ok_or_else(|| {
+                Error::invalid_argument(&format!("Expected bracketed form key, got {:?}", key))
+            })?;
+
+            let mut target = &mut data;
+
+            for segment in segments {
+                target = match segment {
+                    Some(name) => {
+                        if target.is_null() {
+                            *target = JsonValue::new_object();
+                        }
+
+                        match *target {
+                            JsonValue::Object(ref mut object) => &mut object[name],
+                            _ => return Err(Error::invalid_argument(&format!("Conflicting key {:?}, expected Object", key))),
+                        }
+                    },
+                    None => {
+                        if target.is_null() {
+                            *target = JsonValue::new_array();
+                        }
+
+                        match *target {
+                            JsonValue::Array(ref mut vec) => {
+                                vec.push(JsonValue::Null);
+                                vec.last_mut().expect("Was just pushed")
+                            },
+                            _ => return Err(Error::invalid_argument(&format!("Conflicting key {:?}, expected Array", key))),
+                        }
+                    },
+                };
+            }
+
+            if target.is_object() || target.is_array() {
+                return Err(Error::invalid_argument(&format!("Conflicting key {:?}, expected String", key)));
+            }
+
+            *target = value.into();
+        }
+
+        Ok(data)
+    }
+
+    /// Serialize a flat `JsonValue::Object` into a percent-encoded query
+    /// string. Keys are emitted in sorted order, strings, numbers and booleans
+    /// are written as they are, and arrays of those expand into a repeated key
+    /// for every member.
+    ///
+    /// Nested objects, nested arrays and nulls can't be represented in a
+    /// query string and will produce an `Error::InvalidArgument`.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{
+    ///     q: "rust json",
+    ///     page: 2,
+    ///     tag: ["a", "b"]
+    /// };
+    ///
+    /// assert_eq!(data.to_query_string().unwrap(), "page=2&q=rust%20json&tag=a&tag=b");
+    /// # }
+    /// ```
+    pub fn to_query_string(&self) -> Result<String> {
+        let object = match *self {
+            JsonValue::Object(ref object) => object,
+            _ => return Err(Error::wrong_type("Object")),
+        };
+
+        let mut entries: Vec<_> = object.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut qs = String::new();
+
+        for (key, value) in entries {
+            match *value {
+                JsonValue::Array(ref vec) => {
+                    for member in vec {
+                        write_pair(&mut qs, key, member)?;
+                    }
+                },
+                _ => write_pair(&mut qs, key, value)?,
+            }
+        }
+
+        Ok(qs)
+    }
+}
+
+// Split `a[b][]` into `[Some("a"), Some("b"), None]`, where `None` stands
+// for the empty `[]`. Returns `None` for malformed keys.
+fn bracket_segments(key: &str) -> Option<Vec<Option<&str>>> {
+    let (base, mut rest) = match key.find('[') {
+        Some(index) => (&key[..index], &key[index..]),
+        None        => (key, ""),
+    };
+
+    if base.is_empty() || base.contains(']') {
+        return None;
+    }
+
+    let mut segments = vec![Some(base)];
+
+    while !rest.is_empty() {
+        let end = rest.find(']')?;
+        let name = rest.strip_prefix('[')?.get(..end - 1)?;
+
+        if name.contains('[') {
+            return None;
+        }
+
+        segments.push(if name.is_empty() { None } else { Some(name) });
+        rest = &rest[end + 1..];
+    }
+
+    Some(segments)
+}
+
+fn write_pair(qs: &mut String, key: &str, value: &JsonValue) -> Result<()> {
+    if !qs.is_empty() {
+        qs.push('&');
+    }
+
+    percent_encode(qs, key);
+    qs.push('=');
+
+    match *value {
+        JsonValue::Short(ref value) if value.is_raw_number() => qs.push_str(value),
+        JsonValue::Short(ref value)   => percent_encode(qs, value),
+        JsonValue::String(ref value)  => percent_encode(qs, value),
+        JsonValue::Number(ref value)  => write!(qs, "{}", value).expect("Can't fail"),
+        JsonValue::Boolean(ref value) => write!(qs, "{}", value).expect("Can't fail"),
+        _ => return Err(Error::invalid_argument(&format!("Expected String, Number or Boolean for {:?}", key))),
+    }
+
+    Ok(())
+}
+
+// Everything except the RFC 3986 unreserved characters gets escaped.
+fn percent_encode(qs: &mut String, source: &str) {
+    for byte in source.bytes() {
+        match byte {
+            b'A' ..= b'Z' |
+            b'a' ..= b'z' |
+            b'0' ..= b'9' |
+            b'-' | b'_' | b'.' | b'~' => qs.push(byte as char),
+            _ => write!(qs, "%{:02X}", byte).expect("Can't fail"),
+        }
+    }
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0' ..= b'9' => Some(byte - b'0'),
+        b'a' ..= b'f' => Some(byte + 10 - b'a'),
+        b'A' ..= b'F' => Some(byte + 10 - b'A'),
+        _             => None,
+    }
+}
+
+// Malformed escapes, such as `%zz` or a trailing `%`, are kept verbatim.
+// Decoded bytes that don't form valid UTF-8 are replaced with U+FFFD.
+fn percent_decode(source: &str) -> String {
+    let bytes = source.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        index += 1;
+
+        if byte == b'+' {
+            decoded.push(b' ');
+            continue;
+        }
+
+        if byte == b'%' && index + 2 <= bytes.len() {
+            if let (Some(high), Some(low)) = (hex_value(bytes[index]), hex_value(bytes[index + 1])) {
+                decoded.push(high << 4 | low);
+                index += 2;
+                continue;
+            }
+        }
+
+        decoded.push(byte);
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
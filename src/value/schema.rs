@@ -0,0 +1,209 @@
+// This is a private module that contains validation of `JsonValue` against
+// a minimal subset of JSON Schema.
+
+use crate::{ Result, Error };
+use crate::value::JsonValue;
+use crate::value::pointer::escape_token;
+
+impl JsonValue {
+    /// Validate against a minimal subset of JSON Schema, supporting only
+    /// the following keywords, with all others ignored:
+    ///
+    /// - `type`, either a single type name or an array of them, out of
+    ///   `"null"`, `"boolean"`, `"object"`, `"array"`, `"number"`,
+    ///   `"integer"` and `"string"`
+    /// - `enum`, an array of allowed values
+    /// - `required` and `properties` for objects
+    /// - `items`, a single schema for all members, and `minItems` and
+    ///   `maxItems` for arrays
+    /// - `minimum` and `maximum` for numbers, both inclusive
+    ///
+    /// Same as in JSON Schema, keywords only apply to values of their type,
+    /// so `minimum` doesn't reject a string, that's the job of `type`.
+    ///
+    /// Errors on the first violation found, with `Error::UndefinedField`
+    /// carrying the JSON Pointer to a missing required key, or with
+    /// `Error::PatternMismatch` carrying the JSON Pointer to the offending
+    /// value along with a description of what was expected. Errors with
+    /// `Error::InvalidArgument` if the schema itself is malformed.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # use json::Error;
+    /// # fn main() {
+    /// let schema = object!{
+    ///     type: "object",
+    ///     required: ["id"],
+    ///     properties: {
+    ///         id: { type: "integer", minimum: 1 },
+    ///         tags: { type: "array", items: { type: "string" } }
+    ///     }
+    /// };
+    ///
+    /// assert!(object!{ id: 7, tags: ["a"] }.validate_schema(&schema).is_ok());
+    /// assert_eq!(object!{ tags: [] }.validate_schema(&schema), Err(Error::undefined("/id")));
+    /// assert_eq!(object!{ id: 7, tags: [1] }.validate_schema(&schema), Err(Error::PatternMismatch {
+    ///     pointer: "/tags/0".into(),
+    ///     expected: "string".into(),
+    ///     found: "integer".into(),
+    /// }));
+    /// # }
+    /// ```
+    pub fn validate_schema(&self, schema: &JsonValue) -> Result<()> {
+        self.validate_at(schema, &mut String::new())
+    }
+
+    fn validate_at(&self, schema: &JsonValue, pointer: &mut String) -> Result<()> {
+        let schema = match *schema {
+            JsonValue::Object(ref schema) => schema,
+            _ => return Err(Error::invalid_argument(&format!("Expected Object schema, got {}", schema.dump()))),
+        };
+
+        let mismatch = |pointer: &str, expected: String, found: String| Error::PatternMismatch {
+            pointer: pointer.into(),
+            expected,
+            found,
+        };
+
+        if let Some(types) = schema.get("type") {
+            let types = match *types {
+                JsonValue::Array(ref vec) => vec.iter().collect(),
+                _ => vec![types],
+            };
+
+            let mut matched = false;
+
+            for name in &types {
+                matched |= match name.as_str() {
+                    Some("null")    => self.is_null(),
+                    Some("boolean") => self.is_boolean(),
+                    Some("object")  => self.is_object(),
+                    Some("array")   => self.is_array(),
+                    Some("number")  => self.is_number(),
+                    Some("integer") => is_integer(self),
+                    Some("string")  => self.is_string(),
+                    _ => return Err(invalid_keyword("type", name)),
+                };
+            }
+
+            if !matched {
+                let expected: Vec<_> = types.iter().filter_map(|name| name.as_str()).collect();
+
+                return Err(mismatch(pointer, expected.join(" or "), type_name(self).into()));
+            }
+        }
+
+        if let Some(allowed) = schema.get("enum") {
+            let allowed = match *allowed {
+                JsonValue::Array(ref vec) => vec,
+                _ => return Err(invalid_keyword("enum", allowed)),
+            };
+
+            if !allowed.contains(self) {
+                return Err(mismatch(pointer, format!("one of {}", JsonValue::Array(allowed.clone()).dump()), self.dump()));
+            }
+        }
+
+        let len = pointer.len();
+
+        match *self {
+            JsonValue::Object(ref object) => {
+                if let Some(required) = schema.get("required") {
+                    let keys = match *required {
+                        JsonValue::Array(ref vec) if vec.iter().all(JsonValue::is_string) => vec,
+                        _ => return Err(invalid_keyword("required", required)),
+                    };
+
+                    for key in keys.iter().filter_map(JsonValue::as_str) {
+                        if object.get(key).is_none() {
+                            pointer.push('/');
+                            escape_token(pointer, key);
+
+                            return Err(Error::undefined(pointer));
+                        }
+                    }
+                }
+
+                if let Some(properties) = schema.get("properties") {
+                    let properties = match *properties {
+                        JsonValue::Object(ref properties) => properties,
+                        _ => return Err(invalid_keyword("properties", properties)),
+                    };
+
+                    for (key, schema) in properties.iter() {
+                        if let Some(value) = object.get(key) {
+                            pointer.push('/');
+                            escape_token(pointer, key);
+                            value.validate_at(schema, pointer)?;
+                            pointer.truncate(len);
+                        }
+                    }
+                }
+            },
+            JsonValue::Array(ref vec) => {
+                if let Some(min) = schema.get("minItems") {
+                    let min = min.as_usize().ok_or_else(|| invalid_keyword("minItems", min))?;
+
+                    if vec.len() < min {
+                        return Err(mismatch(pointer, format!("at least {} items", min), format!("{} items", vec.len())));
+                    }
+                }
+
+                if let Some(max) = schema.get("maxItems") {
+                    let max = max.as_usize().ok_or_else(|| invalid_keyword("maxItems", max))?;
+
+                    if vec.len() > max {
+                        return Err(mismatch(pointer, format!("at most {} items", max), format!("{} items", vec.len())));
+                    }
+                }
+
+                if let Some(items) = schema.get("items") {
+                    for (index, member) in vec.iter().enumerate() {
+                        pointer.push('/');
+                        pointer.push_str(&index.to_string());
+                        member.validate_at(items, pointer)?;
+                        pointer.truncate(len);
+                    }
+                }
+            },
+            _ if self.is_number() => {
+                let number = self.as_f64().unwrap_or(f64::NAN);
+
+                if let Some(min) = schema.get("minimum") {
+                    if number.is_nan() || number < min.as_f64().ok_or_else(|| invalid_keyword("minimum", min))? {
+                        return Err(mismatch(pointer, format!(">= {}", min), self.dump()));
+                    }
+                }
+
+                if let Some(max) = schema.get("maximum") {
+                    if number.is_nan() || number > max.as_f64().ok_or_else(|| invalid_keyword("maximum", max))? {
+                        return Err(mismatch(pointer, format!("<= {}", max), self.dump()));
+                    }
+                }
+            },
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+fn is_integer(value: &JsonValue) -> bool {
+    value.as_f64().map(|number| number.fract() == 0.0).unwrap_or(false)
+}
+
+fn type_name(value: &JsonValue) -> &'static str {
+    match *value {
+        JsonValue::Null       => "null",
+        JsonValue::Boolean(_) => "boolean",
+        JsonValue::Object(_)  => "object",
+        JsonValue::Array(_)   => "array",
+        _ if value.is_string() => "string",
+        _ if is_integer(value) => "integer",
+        _ => "number",
+    }
+}
+
+fn invalid_keyword(keyword: &str, value: &JsonValue) -> Error {
+    Error::invalid_argument(&format!("Expected valid {:?} schema keyword, got {}", keyword, value.dump()))
+}
@@ -0,0 +1,137 @@
+// This is a private module that contains conversions of timestamps stored
+// in a `JsonValue` to Unix time.
+
+use crate::{ Result, Error };
+use crate::value::JsonValue;
+
+impl JsonValue {
+    /// Get a timestamp as the number of seconds since the Unix epoch. Numbers
+    /// are taken to already be in seconds and have to be integers, erroring
+    /// with `Error::IntegerOutOfRange` otherwise, or with
+    /// `Error::NonFiniteNumber` for NaN. Strings are parsed as
+    /// RFC 3339 date-times, such as `2020-01-01T12:00:00Z` or
+    /// `2020-01-01T13:00:00.5+01:00`, with fractions of a second dropped.
+    /// Errors with `Error::InvalidArgument` if the string isn't a valid
+    /// RFC 3339 date-time, or with `Error::WrongType` if called on anything
+    /// but a number or a string.
+    ///
+    /// ```
+    /// # use json::JsonValue;
+    /// assert_eq!(JsonValue::from(1577880000).as_unix_seconds(), Ok(1577880000));
+    /// assert_eq!(JsonValue::from("2020-01-01T12:00:00Z").as_unix_seconds(), Ok(1577880000));
+    /// assert_eq!(JsonValue::from("2020-01-01T13:00:00.5+01:00").as_unix_seconds(), Ok(1577880000));
+    /// assert!(JsonValue::from("yesterday").as_unix_seconds().is_err());
+    /// ```
+    pub fn as_unix_seconds(&self) -> Result<i64> {
+        if let Some(number) = self.as_f64() {
+            if !number.is_finite() {
+                return Err(Error::NonFiniteNumber);
+            }
+
+            // `i64::MAX` is not representable as `f64`, the nearest float is
+            // one higher, hence the exclusive upper bound.
+            if number.fract() != 0.0 || number < i64::MIN as f64 || number >= i64::MAX as f64 {
+                return Err(Error::IntegerOutOfRange(self.dump()));
+            }
+
+            return Ok(number as i64);
+        }
+
+        match self.as_str() {
+            Some(timestamp) => parse_rfc3339(timestamp).ok_or_else(|| {
+                Error::invalid_argument(&format!("Expected RFC 3339 timestamp, got {:?}", timestamp))
+            }),
+            None => Err(Error::wrong_type("Number or String")),
+        }
+    }
+}
+
+fn parse_rfc3339(timestamp: &str) -> Option<i64> {
+    let bytes = timestamp.as_bytes();
+
+    if bytes.len() < 20
+        || bytes[4] != b'-' || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't' | b' ')
+        || bytes[13] != b':' || bytes[16] != b':' {
+        return None;
+    }
+
+    let year = digits(&bytes[0..4])?;
+    let month = digits(&bytes[5..7])?;
+    let day = digits(&bytes[8..10])?;
+    let hour = digits(&bytes[11..13])?;
+    let minute = digits(&bytes[14..16])?;
+    let second = digits(&bytes[17..19])?;
+
+    // Leap seconds are allowed, and end up the same as the first second
+    // of the following minute.
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day)
+        || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let mut rest = &bytes[19..];
+
+    if rest[0] == b'.' {
+        let fraction = rest[1..].iter().take_while(|byte| byte.is_ascii_digit()).count();
+
+        if fraction == 0 {
+            return None;
+        }
+
+        rest = &rest[1 + fraction..];
+    }
+
+    let offset = match *rest {
+        [b'Z'] | [b'z'] => 0,
+        [sign @ b'+', h1, h2, b':', m1, m2] | [sign @ b'-', h1, h2, b':', m1, m2] => {
+            let hours = digits(&[h1, h2])?;
+            let minutes = digits(&[m1, m2])?;
+
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+
+            let offset = hours * 3600 + minutes * 60;
+
+            if sign == b'-' { -offset } else { offset }
+        },
+        _ => return None,
+    };
+
+    let days = days_from_civil(year, month, day);
+
+    Some(days * 86400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+fn digits(bytes: &[u8]) -> Option<i64> {
+    bytes.iter().try_fold(0, |number, &byte| {
+        if byte.is_ascii_digit() {
+            Some(number * 10 + (byte - b'0') as i64)
+        } else {
+            None
+        }
+    })
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Number of days since 1970-01-01 in the proleptic Gregorian calendar, see
+// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = (month + 9) % 12;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
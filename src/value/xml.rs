@@ -0,0 +1,110 @@
+// This is a private module that contains a simple conversion from
+// `JsonValue` to XML.
+
+use crate::{ Result, Error };
+use crate::value::JsonValue;
+
+impl JsonValue {
+    /// Convert into a simple XML document with a single `root` element,
+    /// with no XML declaration and no whitespace between elements. The
+    /// mapping is as follows:
+    ///
+    /// - every entry of an object becomes a child element named by its key
+    /// - an array repeats its parent element once for every member, so an
+    ///   empty array produces nothing, and nested arrays are flattened
+    /// - strings, numbers and booleans become escaped text content
+    /// - `null` becomes an empty element, such as `<name/>`
+    ///
+    /// Attributes and namespaces are not supported. Errors with
+    /// `Error::InvalidArgument` if `root` or any key is not a valid XML
+    /// element name, which is limited to ASCII letters, digits, `_`, `-` and
+    /// `.`, not starting with a digit, `-` or `.`. Errors with
+    /// `Error::WrongType` if called on an array, as that would produce more
+    /// than one root element.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate json;
+    /// # fn main() {
+    /// let data = object!{ name: "A & B", tags: ["x", "y"], parent: null };
+    ///
+    /// assert_eq!(
+    ///     data.to_simple_xml("config").unwrap(),
+    ///     "<config><name>A &amp; B</name><tags>x</tags><tags>y</tags><parent/></config>"
+    /// );
+    /// # }
+    /// ```
+    pub fn to_simple_xml(&self, root: &str) -> Result<String> {
+        if self.is_array() {
+            return Err(Error::wrong_type("Object or primitive at the root"));
+        }
+
+        let mut xml = String::new();
+
+        write_element(&mut xml, root, self)?;
+
+        Ok(xml)
+    }
+}
+
+fn write_element(xml: &mut String, name: &str, value: &JsonValue) -> Result<()> {
+    if !is_element_name(name) {
+        return Err(Error::invalid_argument(&format!("Expected XML element name, got {:?}", name)));
+    }
+
+    if let JsonValue::Array(ref vec) = *value {
+        for member in vec {
+            write_element(xml, name, member)?;
+        }
+
+        return Ok(());
+    }
+
+    xml.push('<');
+    xml.push_str(name);
+
+    match *value {
+        JsonValue::Null => {
+            xml.push_str("/>");
+            return Ok(());
+        },
+        JsonValue::Object(ref object) => {
+            xml.push('>');
+
+            for (key, value) in object.iter() {
+                write_element(xml, key, value)?;
+            }
+        },
+        _ => {
+            xml.push('>');
+            escape_text(xml, &value.to_display_string());
+        }
+    }
+
+    xml.push_str("</");
+    xml.push_str(name);
+    xml.push('>');
+
+    Ok(())
+}
+
+fn is_element_name(name: &str) -> bool {
+    let mut bytes = name.bytes();
+
+    match bytes.next() {
+        Some(b'a' ..= b'z') | Some(b'A' ..= b'Z') | Some(b'_') => {},
+        _ => return false,
+    }
+
+    bytes.all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-' || byte == b'.')
+}
+
+fn escape_text(xml: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            _   => xml.push(ch),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+use json::{ parse_borrowed, BorrowedValue };
+
+#[test]
+fn borrowed_plain_strings() {
+    let source = r#"{"name":"foo","tags":["a","b"],"count":3,"ok":true,"none":null}"#;
+    let data = parse_borrowed(source).unwrap();
+
+    match data["name"].as_cow_str() {
+        Some(&Cow::Borrowed(name)) => assert_eq!(name, "foo"),
+        other                      => panic!("Expected borrowed string, got {:?}", other),
+    }
+
+    assert_eq!(data["tags"].members().count(), 2);
+    assert_eq!(data["tags"][1].as_str(), Some("b"));
+    assert_eq!(data["count"].as_f64(), Some(3.0));
+    assert_eq!(data["ok"].as_bool(), Some(true));
+    assert!(data["none"].is_null());
+    assert!(data["missing"].is_null());
+}
+
+#[test]
+fn borrowed_escaped_strings_are_owned() {
+    let data = parse_borrowed(r#"["line\nbreak", "\u00e9"]"#).unwrap();
+
+    for (member, expected) in data.members().zip(&["line\nbreak", "é"]) {
+        match member.as_cow_str() {
+            Some(&Cow::Owned(ref string)) => assert_eq!(string, expected),
+            other                         => panic!("Expected owned string, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn borrowed_escaped_keys() {
+    let data = parse_borrowed(r#"{"a\"b":1,"c":{"d":[]}}"#).unwrap();
+
+    assert_eq!(data["a\"b"].as_f64(), Some(1.0));
+    assert_eq!(data["c"]["d"], BorrowedValue::Array(Vec::new()));
+}
+
+#[test]
+fn borrowed_errors() {
+    assert!(parse_borrowed(r#"{"a":1"#).is_err());
+    assert!(parse_borrowed(r#"[1,2] 3"#).is_err());
+}
+
+#[test]
+fn borrowed_into_owned() {
+    let source = r#"{"a":"plain","b":"esc\u0061ped","c":[1,true,null,{"d":[]}],"a":"last"}"#;
+    let owned: json::JsonValue = parse_borrowed(source).unwrap().into();
+
+    assert_eq!(owned, json::parse(source).unwrap());
+    assert_eq!(owned["a"], "last");
+    assert_eq!(owned["b"], "escaped");
+    assert_eq!(owned["c"][3]["d"], json::JsonValue::new_array());
+}
@@ -0,0 +1,68 @@
+#[macro_use]
+extern crate json;
+
+use json::{ parse, parse_json5, Error };
+
+#[test]
+fn json5_unquoted_keys() {
+    let data = parse_json5("{ key: 'value', _other$1: 1, $: null, }").unwrap();
+
+    assert_eq!(data, object!{ key: "value", "_other$1" => 1, "$" => null });
+}
+
+#[test]
+fn json5_single_quoted_strings() {
+    let data = parse_json5(r#"['it\'s', 'say "hi"', "it's", 'é\n', '']"#).unwrap();
+
+    assert_eq!(data, array!["it's", "say \"hi\"", "it's", "é\n", ""]);
+    assert_eq!(parse_json5(r#"{ 'quoted key': true }"#).unwrap(), object!{ "quoted key" => true });
+}
+
+#[test]
+fn json5_comments_and_trailing_commas() {
+    let source = "
+        // Server configuration
+        {
+            host: 'localhost', /* default */
+            ports: [80, 443,],
+            nested: { deep: [[],], },
+        }
+        // end
+    ";
+
+    assert_eq!(parse_json5(source).unwrap(), object!{
+        host: "localhost",
+        ports: [80, 443],
+        nested: { deep: [[]] }
+    });
+}
+
+#[test]
+fn json5_accepts_strict_json() {
+    let source = r#"{"a":[1,-2.5e3,true,false,null,"😀"],"b":{}}"#;
+
+    assert_eq!(parse_json5(source).unwrap(), parse(source).unwrap());
+}
+
+#[test]
+fn json5_errors() {
+    assert_eq!(parse_json5("{ a: 1 /* unterminated"), Err(Error::UnexpectedEndOfJson));
+    assert_eq!(parse_json5("[1] x"), Err(Error::UnexpectedCharacter { ch: 'x', line: 1, column: 5 }));
+    assert_eq!(parse_json5("{ 1a: 1 }"), Err(Error::UnexpectedCharacter { ch: '1', line: 1, column: 3 }));
+    assert_eq!(parse_json5("[,]"), Err(Error::UnexpectedCharacter { ch: ',', line: 1, column: 2 }));
+    assert_eq!(parse_json5("[1,,]"), Err(Error::UnexpectedCharacter { ch: ',', line: 1, column: 4 }));
+    assert_eq!(parse_json5("[01]"), Err(Error::UnexpectedCharacter { ch: '1', line: 1, column: 3 }));
+    assert_eq!(parse_json5("[truthy]"), Err(Error::UnexpectedCharacter { ch: 't', line: 1, column: 2 }));
+    assert_eq!(parse_json5("'open"), Err(Error::UnexpectedEndOfJson));
+    assert_eq!(parse_json5("1 / 2"), Err(Error::UnexpectedCharacter { ch: '/', line: 1, column: 3 }));
+}
+
+#[test]
+fn strict_parse_rejects_json5() {
+    assert!(parse("{ key: 1 }").is_err());
+    assert!(parse("['value']").is_err());
+    assert!(parse("[1,]").is_err());
+    assert!(parse(r#"{"a":1,}"#).is_err());
+    assert!(parse("// comment\n1").is_err());
+    assert!(parse("1 /* comment */").is_err());
+}
@@ -125,3 +125,93 @@ fn as_fixed_point_i64() {
 fn convert_f64_precision() {
     assert_eq!(unsafe { Number::from_parts_unchecked(true, 4750000000000001, -18) }, 0.004750000000000001);
 }
+
+#[test]
+fn number_from_signed_min() {
+    assert_eq!(Number::from(i8::MIN), -128);
+    assert_eq!(Number::from(i32::MIN).as_parts(), (false, 2147483648, 0));
+    assert_eq!(Number::from(i64::MIN).as_parts(), (false, 9223372036854775808, 0));
+}
+
+// A tiny xorshift generator, deterministic so that failures are reproducible
+fn random_bits(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+fn assert_round_trip(float: f64) {
+    let serialized = json::stringify(float);
+    let parsed = json::parse(&serialized).unwrap().as_f64().unwrap();
+
+    assert_eq!(parsed.to_bits(), float.to_bits(), "{:e} serialized as {}", float, serialized);
+}
+
+#[test]
+fn f64_round_trip_random_bits() {
+    let mut state = 0x2545F4914F6CDD1D;
+
+    for _ in 0..100_000 {
+        let float = f64::from_bits(random_bits(&mut state));
+
+        if float.is_finite() {
+            assert_round_trip(float);
+        }
+    }
+}
+
+#[test]
+fn f64_round_trip_subnormals() {
+    let mut state = 0x9E3779B97F4A7C15;
+
+    for _ in 0..10_000 {
+        // Zero exponent bits make for a subnormal number
+        let bits = random_bits(&mut state) & 0x800F_FFFF_FFFF_FFFF;
+
+        if bits & 0x7FFF_FFFF_FFFF_FFFF != 0 {
+            assert_round_trip(f64::from_bits(bits));
+        }
+    }
+}
+
+#[test]
+fn f64_round_trip_extremes() {
+    for &float in &[
+        f64::MAX,
+        f64::MIN,
+        f64::MIN_POSITIVE,
+        f64::EPSILON,
+        5e-324,
+        -5e-324,
+        2.2250738585072009e-308,
+        1.7976931348623155e308,
+        9007199254740993.0,
+        0.1,
+        1.0 / 3.0,
+        123456789012345680000.0,
+    ] {
+        assert_round_trip(float);
+    }
+}
+
+#[test]
+fn f64_from_large_mantissas() {
+    let mut state = 0xD1B54A32D192ED03;
+
+    for _ in 0..100_000 {
+        let bits = random_bits(&mut state);
+        let mantissa = bits >> (bits % 12);
+        let exponent = ((bits >> 56) % 45) as i16 - 22;
+        let number = unsafe { Number::from_parts_unchecked(true, mantissa, exponent) };
+        let expected: f64 = format!("{}e{}", mantissa, exponent).parse().unwrap();
+
+        assert_eq!(f64::from(number).to_bits(), expected.to_bits(), "{}e{}", mantissa, exponent);
+    }
+
+    // Halfway between two doubles, rounding to even in both directions
+    assert_eq!(f64::from(Number::from_parts(true, 9007199254740993, 0)), 9007199254740992.0);
+    assert_eq!(f64::from(Number::from_parts(true, 9007199254740995, 0)), 9007199254740996.0);
+    assert_eq!(f64::from(Number::from_parts(true, 30000000000000004, -17)), 0.30000000000000004);
+    assert_eq!(f64::from(Number::from_parts(true, 18446744073709551615, 20)), 1.8446744073709552e39);
+}
@@ -378,3 +378,526 @@ fn does_not_panic_on_huge_numbers() {
 
     let _ = json::parse(&string);
 }
+
+#[test]
+fn parse_partial_returns_remainder() {
+    let (value, rest) = json::parse_partial(r#"{"a":1}rest"#).unwrap();
+
+    assert_eq!(value, object!{ a: 1 });
+    assert_eq!(rest, "rest");
+
+    let (value, rest) = json::parse_partial("  42,43").unwrap();
+
+    assert_eq!(value, 42);
+    assert_eq!(rest, ",43");
+
+    assert!(json::parse_partial(r#"{"a":"#).is_err());
+}
+
+#[test]
+fn reusable_parser() {
+    let mut parser = json::Parser::new();
+
+    assert_eq!(parser.parse(r#"{"a":["b\nc",{"d":"é"}]}"#).unwrap(), object!{
+        a: ["b\nc", { d: "é" }]
+    });
+
+    // Errors midway through nested structures should not leak into the next parse
+    assert!(parser.parse(r#"[[{"a":"\t"#).is_err());
+    assert!(parser.parse(r#"[1,{"b":2"#).is_err());
+
+    assert_eq!(parser.parse(r#"["\"x\"",2]"#).unwrap(), array!["\"x\"", 2]);
+    assert_eq!(parser.parse("null").unwrap(), Null);
+}
+
+#[test]
+fn unescape_string() {
+    assert_eq!(json::unescape_string(r#""\u0001\u001f\t\r\n\\\"\/""#).unwrap(), "\u{1}\u{1f}\t\r\n\\\"/");
+    assert_eq!(json::unescape_string(r#""\ud83d\ude00""#).unwrap(), "😀");
+    assert_eq!(json::unescape_string(&json::escape_string("a\u{7}b")).unwrap(), "a\u{7}b");
+
+    assert!(json::unescape_string("foo").is_err());
+    assert!(json::unescape_string(r#""foo"#).is_err());
+    assert!(json::unescape_string(r#""foo" "bar""#).is_err());
+    assert!(json::unescape_string(r#""\ud83d""#).is_err());
+}
+
+#[test]
+fn parse_array_streaming() {
+    let mut source = String::from("[");
+
+    for i in 0..10000 {
+        if i != 0 {
+            source.push_str(", ");
+        }
+        source.push_str(&format!(r#"{{"value":{}}}"#, i));
+    }
+
+    source.push(']');
+
+    let mut count = 0;
+    let mut sum = 0;
+
+    json::parse_array_streaming(&source, |member| {
+        count += 1;
+        sum += member["value"].as_u64().unwrap();
+        Ok(())
+    }).unwrap();
+
+    assert_eq!(count, 10000);
+    assert_eq!(sum, 49995000);
+
+    json::parse_array_streaming(" [ ] ", |_| panic!("No members")).unwrap();
+}
+
+#[test]
+fn parse_array_streaming_errors() {
+    let noop = |_| Ok(());
+
+    assert_eq!(json::parse_array_streaming(r#"{"a":1}"#, noop), Err(json::Error::wrong_type("Array")));
+    assert!(json::parse_array_streaming("[1,2", noop).is_err());
+    assert!(json::parse_array_streaming("[1,]", noop).is_err());
+    assert!(json::parse_array_streaming("[1] 2", noop).is_err());
+
+    let mut seen = Vec::new();
+    let result = json::parse_array_streaming("[1,2,3]", |member| {
+        if member == 2 {
+            return Err(json::Error::wrong_type("Odd number"));
+        }
+        seen.push(member);
+        Ok(())
+    });
+
+    assert_eq!(result, Err(json::Error::wrong_type("Odd number")));
+    assert_eq!(seen, vec![1]);
+}
+
+#[test]
+fn parse_object_streaming() {
+    let mut source = String::from("{");
+
+    for i in 0..10000 {
+        if i != 0 {
+            source.push_str(", ");
+        }
+        source.push_str(&format!(r#""id-{}":{{"value":{}}}"#, i, i));
+    }
+
+    source.push('}');
+
+    let mut count = 0;
+    let mut sum = 0;
+
+    json::parse_object_streaming(&source, |key, value| {
+        assert_eq!(key, format!("id-{}", value["value"]));
+        count += 1;
+        sum += value["value"].as_u64().unwrap();
+        Ok(())
+    }).unwrap();
+
+    assert_eq!(count, 10000);
+    assert_eq!(sum, 49995000);
+
+    let mut keys = Vec::new();
+
+    json::parse_object_streaming(r#" { "a\u0062" : 1 , "a" : 2, "a": 3 } "#, |key, _| {
+        keys.push(key.to_string());
+        Ok(())
+    }).unwrap();
+
+    assert_eq!(keys, ["ab", "a", "a"]);
+
+    json::parse_object_streaming(" { } ", |_, _| panic!("No entries")).unwrap();
+}
+
+#[test]
+fn parse_object_streaming_errors() {
+    let noop = |_: &str, _| Ok(());
+
+    assert_eq!(json::parse_object_streaming("[1]", noop), Err(json::Error::wrong_type("Object")));
+    assert!(json::parse_object_streaming(r#"{"a":1"#, noop).is_err());
+    assert!(json::parse_object_streaming(r#"{"a":1,}"#, noop).is_err());
+    assert!(json::parse_object_streaming(r#"{"a" 1}"#, noop).is_err());
+    assert!(json::parse_object_streaming(r#"{a:1}"#, noop).is_err());
+    assert!(json::parse_object_streaming(r#"{"a":1} 2"#, noop).is_err());
+
+    let result = json::parse_object_streaming(r#"{"a":1,"b":2}"#, |key, _| {
+        Err(json::Error::wrong_type(key))
+    });
+
+    assert_eq!(result, Err(json::Error::wrong_type("a")));
+}
+
+#[test]
+fn parse_exact_integers() {
+    assert_eq!(
+        json::parse_exact_integers("12345678901234567890"),
+        Err(json::Error::IntegerOverflow("12345678901234567890".into()))
+    );
+    assert_eq!(
+        json::parse_exact_integers(r#"{"id":-9007199254740993}"#),
+        Err(json::Error::IntegerOverflow("-9007199254740993".into()))
+    );
+    assert_eq!(
+        json::parse_exact_integers("[9007199254740992, -9007199254740992, 0, 12345678901234567890.5, 1e30]").unwrap(),
+        json::parse("[9007199254740992, -9007199254740992, 0, 12345678901234567890.5, 1e30]").unwrap()
+    );
+    assert!(json::parse("12345678901234567890").is_ok());
+}
+
+#[test]
+fn parse_with_dedup_callback() {
+    let mut duplicates = Vec::new();
+
+    let data = json::parse_with_dedup_callback(r#"{"a":1,"a":2}"#, |key| {
+        duplicates.push(key.to_string());
+    }).unwrap();
+
+    assert_eq!(data, object!{ a: 2 });
+    assert_eq!(duplicates, ["a"]);
+
+    duplicates.clear();
+
+    let data = json::parse_with_dedup_callback(r#"[{"a":1,"b":2},{"b":3,"b":4,"c":{"b":5}}]"#, |key| {
+        duplicates.push(key.to_string());
+    }).unwrap();
+
+    assert_eq!(data, array![{ a: 1, b: 2 }, { b: 4, c: { b: 5 } }]);
+    assert_eq!(duplicates, ["b"]);
+
+    let mut count = 0;
+
+    assert!(json::parse_with_dedup_callback(r#"{"a":1,"a":2,"#, |_| count += 1).is_err());
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn parse_preserve_numbers() {
+    let source = r#"[1.000,123456789012345678901234567890,-0.0,0,1E+2,-12.50e-3]"#;
+    let data = json::parse_preserve_numbers(source).unwrap();
+
+    assert_eq!(data[0], JsonValue::raw_number("1.000").unwrap());
+    assert_eq!(data[0].as_raw_number(), Some("1.000"));
+    assert_eq!(data[1].as_raw_number(), Some("123456789012345678901234567890"));
+    assert_eq!(data[0], 1);
+    assert_eq!(data[0], json::from(1.0));
+    assert_eq!(data[2], data[3]);
+    assert_eq!(data[4], json::parse_preserve_numbers("100").unwrap());
+    assert_ne!(data[0], data[3]);
+    assert_eq!(data[1].to_string(), "123456789012345678901234567890");
+    assert_eq!(data.dump(), source);
+    assert_eq!(data.pretty(0), "[\n1.000,\n123456789012345678901234567890,\n-0.0,\n0,\n1E+2,\n-12.50e-3\n]");
+
+    assert!(data[0].is_number());
+    assert!(!data[0].is_string());
+    assert_eq!(data[0].as_str(), None);
+    assert_ne!(data[0], "1.000");
+    assert_ne!(data[0], JsonValue::from("1.000"));
+    assert_eq!(json::from(1).as_raw_number(), None);
+    assert_eq!(json::from("1").as_raw_number(), None);
+    assert_eq!(data.to_truncated_string(2, 1), "[1.000,123456789012345678901234567890,…(+4 more)]");
+    assert_eq!(data[1].clone().take_string(), None);
+    assert_eq!(data[0].as_f64(), Some(1.0));
+    assert_eq!(data[2].as_f64(), Some(-0.0));
+    assert_eq!(data[4].as_f64(), Some(100.0));
+    assert_eq!(data[5].as_fixed_point_i64(4), Some(-125));
+    assert!(data[3].is_empty());
+
+    assert_eq!(json::parse_preserve_numbers(r#"{"a":{"b":[-1]}}"#).unwrap().dump(), r#"{"a":{"b":[-1]}}"#);
+    assert!(json::parse_preserve_numbers("01").is_err());
+    assert!(json::parse_preserve_numbers("[1.]").is_err());
+    assert!(json::parse_preserve_numbers("-").is_err());
+    assert!(json::parse_preserve_numbers("[-a]").is_err());
+
+    let long = format!("[\n1,\n{}]", "1".repeat(31));
+    assert_eq!(json::parse_preserve_numbers(&long).unwrap_err(), json::Error::InvalidSyntax {
+        line: 3,
+        expected: format!("number literal of at most 30 bytes, got {}", "1".repeat(31)),
+    });
+
+    assert_eq!(JsonValue::raw_number("-12.50e-3").unwrap().as_raw_number(), Some("-12.50e-3"));
+    assert_eq!(JsonValue::raw_number("").unwrap_err(), json::Error::UnexpectedEndOfJson);
+    assert_eq!(JsonValue::raw_number(" 1").unwrap_err(), json::Error::UnexpectedCharacter { ch: ' ', line: 1, column: 1 });
+    assert_eq!(JsonValue::raw_number("1 ").unwrap_err(), json::Error::UnexpectedCharacter { ch: ' ', line: 1, column: 2 });
+    assert!(JsonValue::raw_number("\"1\"").is_err());
+    assert!(JsonValue::raw_number("1,2").is_err());
+    assert!(JsonValue::raw_number("NaN").is_err());
+    assert!(JsonValue::raw_number(&"1".repeat(31)).is_err());
+}
+
+#[test]
+fn parse_with_limits() {
+    let source = r#"{"a":[1,2,3],"b":{"c":true,"d":null}}"#;
+
+    assert_eq!(json::parse_with_limits(source, 2, 8).unwrap(), parse(source).unwrap());
+    assert_eq!(json::parse_with_limits(source, 1, 8), Err(json::Error::ExceededKeyLimit(1)));
+    assert_eq!(json::parse_with_limits(source, 2, 7), Err(json::Error::ExceededNodeLimit(7)));
+
+    // Duplicate keys only count once
+    assert!(json::parse_with_limits(r#"{"a":1,"a":2,"a":3}"#, 1, 10).is_ok());
+
+    assert_eq!(json::parse_with_limits(r#"{"a":1}"#, 0, 10), Err(json::Error::ExceededKeyLimit(0)));
+    assert!(json::parse_with_limits("{}", 0, 1).is_ok());
+    assert_eq!(json::parse_with_limits("[]", 0, 0), Err(json::Error::ExceededNodeLimit(0)));
+}
+
+#[test]
+fn parse_reject_trailing() {
+    assert_eq!(json::parse_reject_trailing("true false"), Err(json::Error::TrailingData { offset: 5 }));
+    assert_eq!(json::parse_reject_trailing(r#"{"a":1}{"b":2}"#), Err(json::Error::TrailingData { offset: 7 }));
+    assert_eq!(json::parse_reject_trailing(" [1] \n\t x"), Err(json::Error::TrailingData { offset: 8 }));
+    assert_eq!(json::parse_reject_trailing(" [1] \r\n").unwrap(), array![1]);
+    assert_eq!(json::parse_reject_trailing("[1,"), Err(json::Error::UnexpectedEndOfJson));
+    assert!(json::parse_reject_trailing("[1}").is_err());
+    assert_ne!(json::parse("true false"), Err(json::Error::TrailingData { offset: 5 }));
+}
+
+#[test]
+fn parse_with_location_map() {
+    let source = "{\n  \"config\": {\n    \"name\": \"app\",\n    \"a/b\": [1, -2.5e3, null]\n  },\n  \"esc\\u0061ped\" : true\n}";
+    let (data, spans) = json::parse_with_location_map(source).unwrap();
+
+    assert_eq!(data, parse(source).unwrap());
+
+    let slice = |pointer: &str| {
+        let (start, end) = spans[pointer];
+        &source[start..end]
+    };
+
+    assert_eq!(spans["/config/name"], (28, 33));
+    assert_eq!(slice("/config/name"), "\"app\"");
+    assert_eq!(slice("/config/a~1b"), "[1, -2.5e3, null]");
+    assert_eq!(slice("/config/a~1b/1"), "-2.5e3");
+    assert_eq!(slice("/config/a~1b/2"), "null");
+    assert_eq!(slice("/escaped"), "true");
+    assert_eq!(slice(""), source);
+    assert_eq!(spans.len(), 8);
+
+    let (_, spans) = json::parse_with_location_map(r#"{"a":1,"a":22}"#).unwrap();
+
+    assert_eq!(spans["/a"], (11, 13));
+
+    let source = r#"{"a":{"b":[1,{"c":2}],"bb":3},"ab":4,"a":[5]}"#;
+    let (data, spans) = json::parse_with_location_map(source).unwrap();
+
+    assert_eq!(data["a"], array![5]);
+    assert_eq!(spans.keys().collect::<Vec<_>>(), vec!["", "/a", "/a/0", "/ab"]);
+    assert_eq!(spans["/a/0"], (42, 43));
+
+    assert!(json::parse_with_location_map("[1,").is_err());
+}
+
+#[test]
+fn parse_recover() {
+    let source = "{\n  \"a\": [1, tru, 3],\n  \"b\": {\"c\" 2},\n  \"d\": \"ok\"\n}";
+    let (value, errors) = json::parse_recover(source);
+
+    assert_eq!(value, Some(object!{ a: [1, null, 3], b: { c: null }, d: "ok" }));
+    assert_eq!(errors, vec![
+        json::Error::UnexpectedCharacter { ch: ',', line: 2, column: 15 },
+        json::Error::UnexpectedCharacter { ch: '2', line: 3, column: 13 },
+    ]);
+
+    let (value, errors) = json::parse_recover(r#"{"a":[1,2,],"b":}"#);
+
+    assert_eq!(value, Some(object!{ a: [1, 2], b: null }));
+    assert_eq!(errors.len(), 2);
+
+    let (value, errors) = json::parse_recover(r#"[1, {"a": true"#);
+
+    assert_eq!(value, Some(array![1, { a: true }]));
+    assert_eq!(errors, vec![json::Error::UnexpectedEndOfJson]);
+
+    let (value, errors) = json::parse_recover(r#"["é\u0000é", "ok"] x"#);
+
+    assert_eq!(value, Some(array!["é\u{0}é", "ok"]));
+    assert_eq!(errors, vec![json::Error::UnexpectedCharacter { ch: 'x', line: 1, column: 20 }]);
+
+    assert_eq!(json::parse_recover("nulx"), (None, vec![json::Error::UnexpectedCharacter { ch: 'x', line: 1, column: 4 }]));
+    assert_eq!(json::parse_recover("nul"), (None, vec![json::Error::UnexpectedEndOfJson]));
+    assert_eq!(json::parse_recover("  "), (None, vec![json::Error::UnexpectedEndOfJson]));
+    assert_eq!(json::parse_recover("null"), (Some(Null), vec![]));
+
+    let (value, errors) = json::parse_recover(" }");
+    assert_eq!(value, None);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors, vec![json::Error::UnexpectedCharacter { ch: '}', line: 1, column: 2 }]);
+
+    let (value, errors) = json::parse_recover("]");
+    assert_eq!(value, None);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors, vec![json::Error::UnexpectedCharacter { ch: ']', line: 1, column: 1 }]);
+
+    let (_, errors) = json::parse_recover("}]");
+    assert_eq!(errors.len(), 2);
+
+    let source = r#"{"a":[1,2.5,"x",{"b":null}],"c":true}"#;
+
+    assert_eq!(json::parse_recover(source), (Some(parse(source).unwrap()), vec![]));
+
+    // Long runs of stray characters neither recurse nor rescan the source
+    let source = format!("[1{}\n]", "}".repeat(100_000));
+    let (value, errors) = json::parse_recover(&source);
+
+    assert_eq!(value, Some(array![1]));
+    assert_eq!(errors.len(), 100_000);
+    assert_eq!(errors[99_999], json::Error::UnexpectedCharacter { ch: '}', line: 1, column: 100_002 });
+}
+
+#[test]
+fn parse_lenient_numbers() {
+    let data = json::parse_lenient_numbers(r#"{"a": NaN, "b": [Infinity, -Infinity], "c": -1.5}"#).unwrap();
+
+    assert!(data["a"].as_f64().unwrap().is_nan());
+    assert_eq!(data["b"][0].as_f64(), Some(f64::INFINITY));
+    assert_eq!(data["b"][1].as_f64(), Some(f64::NEG_INFINITY));
+    assert_eq!(data["b"][0].as_f32(), Some(f32::INFINITY));
+    assert_eq!(data["c"], -1.5);
+
+    // Serializes into valid JSON that keeps being infinite
+    assert_eq!(data.dump(), r#"{"a":null,"b":[1e32767,-1e32767],"c":-1.5}"#);
+    assert_eq!(parse(&data["b"].dump()).unwrap()[1].as_f64(), Some(f64::NEG_INFINITY));
+
+    // Comparing against finite numbers neither overflows nor matches
+    assert_ne!(data["b"][0], 1);
+    assert_ne!(data["b"][0], 0.5);
+    assert_ne!(data["b"][0], json::number::Number::from_parts(true, 1, i16::MIN));
+    assert_ne!(data["b"][0], json::number::Number::from_parts(true, 1, 32367));
+    assert_ne!(data["b"][1], -1);
+    assert_eq!(data["b"][0], data["b"][0].clone());
+
+    assert!(json::parse_lenient_numbers("Infinit").is_err());
+    assert!(json::parse_lenient_numbers("nan").is_err());
+    assert!(json::parse_lenient_numbers("-NaN").is_err());
+
+    for source in &["NaN", "Infinity", "-Infinity", "[1, NaN]"] {
+        assert!(parse(source).is_err());
+    }
+
+    assert_eq!(parse("[NaN]"), Err(json::Error::UnexpectedCharacter { ch: 'N', line: 1, column: 2 }));
+}
+
+#[test]
+fn extract_pointer() {
+    let big: Vec<String> = (0..10_000).map(|i| format!(r#"{{"n":{},"s":"x\"]}}","e":[[],{{}}]}}"#, i)).collect();
+    let source = format!(
+        r#"{{"skip": [{}], "other": {{"data": 1}}, "data": {{"items": [{{"id": 1}}, {{"id": "second", "x": [1, 2]}}], "a/b~": null}}, "after": "#,
+        big.join(",")
+    );
+
+    // The document is cut off after the target, which is never looked at
+    assert!(parse(&source).is_err());
+
+    assert_eq!(json::extract_pointer(&source, "/data/items/1/id").unwrap(), "second");
+    assert_eq!(json::extract_pointer(&source, "/data/items/0").unwrap(), object!{ id: 1 });
+    assert_eq!(json::extract_pointer(&source, "/skip/9999/s").unwrap(), "x\"]}");
+    assert_eq!(json::extract_pointer(&source, "/skip/5/e").unwrap(), array![[], {}]);
+    assert_eq!(json::extract_pointer(&source, "/data/a~1b~0").unwrap(), json::Null);
+    assert_eq!(json::extract_pointer(&source, "/other/data").unwrap(), 1);
+
+    for pointer in &["/data/items/2", "/data/items/01", "/data/missing", "/data/items/0/id/x", "/data/items/x", "/skip/10000"] {
+        assert_eq!(json::extract_pointer(&source, pointer), Err(json::Error::undefined(pointer)), "{}", pointer);
+    }
+
+    assert_eq!(json::extract_pointer("[1, [2, 3], 4]", "/2").unwrap(), 4);
+    assert_eq!(json::extract_pointer(" [1] ", "").unwrap(), array![1]);
+    assert_eq!(json::extract_pointer("{}", "/a"), Err(json::Error::undefined("/a")));
+    assert_eq!(json::extract_pointer("[]", "/0"), Err(json::Error::undefined("/0")));
+    assert_eq!(json::extract_pointer("[1]", "0"), Err(json::Error::invalid_argument(r#"Expected JSON Pointer, got "0""#)));
+    assert_eq!(json::extract_pointer(r#"{"a": [1, "#, "/b"), Err(json::Error::UnexpectedEndOfJson));
+    assert!(json::extract_pointer(r#"{"a": , "b": 1}"#, "/b").is_err());
+    assert!(json::extract_pointer(r#"{"a": 1 "b": 1}"#, "/b").is_err());
+}
+
+#[test]
+fn parse_relaxed_numbers() {
+    let data = json::parse_relaxed_numbers(r#"{"a": +5, "b": [0x1F, 0XfF, -0x10, +0x0], "c": [1., .5, -.5, +1.e2], "d": -12.5e-1}"#).unwrap();
+
+    assert_eq!(data, object!{
+        a: 5,
+        b: [31, 255, -16, 0],
+        c: [1, 0.5, -0.5, 100],
+        d: -1.25
+    });
+
+    assert_eq!(json::parse_relaxed_numbers("0xFFFFFFFFFFFFFFFF").unwrap().as_u64(), Some(u64::MAX));
+    assert_eq!(json::parse_relaxed_numbers("0x10000000000000000"), Err(json::Error::IntegerOverflow("0x10000000000000000".into())));
+    assert_eq!(json::parse_relaxed_numbers("[1, 2]").unwrap(), array![1, 2]);
+
+    assert_eq!(json::parse_relaxed_numbers("[0x]"), Err(json::Error::UnexpectedCharacter { ch: ']', line: 1, column: 4 }));
+    assert_eq!(json::parse_relaxed_numbers("[.]"), Err(json::Error::UnexpectedCharacter { ch: ']', line: 1, column: 3 }));
+    assert_eq!(json::parse_relaxed_numbers("[012]"), Err(json::Error::UnexpectedCharacter { ch: '1', line: 1, column: 3 }));
+    assert_eq!(json::parse_relaxed_numbers("[1e]"), Err(json::Error::UnexpectedCharacter { ch: ']', line: 1, column: 4 }));
+    assert_eq!(json::parse_relaxed_numbers("+"), Err(json::Error::UnexpectedEndOfJson));
+    assert!(json::parse_relaxed_numbers("++1").is_err());
+    assert!(json::parse_relaxed_numbers("0x1G").is_err());
+
+    for source in &["+5", "0x1F", "1.", ".5", "-.5", "[1., 2]"] {
+        assert!(parse(source).is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn parse_chunks() {
+    let source = r#"{"name":"Ja\"ne ✓ é","age":-12.5e1,"tags":[true,null,"x"],"deep":{"a":[]}}"#;
+    let expected = parse(source).unwrap();
+
+    // Split between every pair of characters, mid literal, number or escape
+    for (at, _) in source.char_indices() {
+        let (head, tail) = source.split_at(at);
+
+        assert_eq!(json::parse_chunks(&[head, tail]).unwrap(), expected, "split at {}", at);
+    }
+
+    assert_eq!(json::parse_chunks(&[r#"["long str"#, "", r#"ing"]"#]).unwrap(), array!["long string"]);
+    assert_eq!(json::parse_chunks(&["1", "0", "0"]).unwrap(), 100);
+
+    assert_eq!(json::parse_chunks(&[]), Err(json::Error::UnexpectedEndOfJson));
+    assert_eq!(json::parse_chunks(&["[\"ab", "c"]), Err(json::Error::UnexpectedEndOfJson));
+    assert_eq!(json::parse_chunks(&["[tr", "u]"]), Err(json::Error::UnexpectedCharacter { ch: ']', line: 1, column: 5 }));
+    assert_eq!(json::parse_chunks(&["[1,", "\n 2x]"]), Err(json::Error::UnexpectedCharacter { ch: 'x', line: 2, column: 3 }));
+    assert_eq!(json::parse_chunks(&["[1,", "]"]), Err(json::Error::UnexpectedCharacter { ch: ']', line: 1, column: 4 }));
+    assert_eq!(json::parse_chunks(&["{}", " {}"]), Err(json::Error::UnexpectedCharacter { ch: '{', line: 1, column: 4 }));
+}
+
+#[test]
+fn parse_with_reviver() {
+    let mut visited = Vec::new();
+
+    let data = json::parse_with_reviver(r#"{"a":"1","b":["2","x",{"c":"-3.5"}],"drop":true}"#, |key, value| {
+        visited.push(key.to_string());
+
+        if key == "drop" {
+            return None;
+        }
+
+        match value.as_str().and_then(|s| s.parse::<f64>().ok()) {
+            Some(number) => Some(number.into()),
+            None         => Some(value),
+        }
+    }).unwrap();
+
+    assert_eq!(data, object!{ a: 1, b: [2, "x", { c: -3.5 }] });
+    assert_eq!(visited, ["a", "0", "1", "c", "2", "b", "drop", ""]);
+
+    // Dropped array members don't leave holes
+    let data = json::parse_with_reviver("[1, null, 2, null]", |_, value| {
+        if value.is_null() { None } else { Some(value) }
+    }).unwrap();
+
+    assert_eq!(data, array![1, 2]);
+    assert_eq!(json::parse_with_reviver("[1]", |_, _| None).unwrap(), Null);
+    assert!(json::parse_with_reviver("[1,", |_, value| Some(value)).is_err());
+}
+
+#[test]
+fn parse_keeps_key_order() {
+    let source = r#"{"b":1,"a":2,"c":{"z":true,"y":false}}"#;
+    let data = parse(source).unwrap();
+
+    let keys: Vec<_> = data.entries().map(|(key, _)| key).collect();
+
+    assert_eq!(keys, ["b", "a", "c"]);
+    assert_eq!(data["c"].entries().map(|(key, _)| key).collect::<Vec<_>>(), ["z", "y"]);
+    assert_eq!(data.dump(), source);
+}
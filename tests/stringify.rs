@@ -247,3 +247,224 @@ fn stringify_pretty_object() {
     assert_eq!(object.pretty(2), expected);
     assert_eq!(stringify_pretty(object, 2), expected);
 }
+
+#[test]
+fn escape_string() {
+    assert_eq!(json::escape_string("foo"), r#""foo""#);
+    assert_eq!(json::escape_string("\u{1}\u{1f}\t\r\n\\\"/"), r#""\u0001\u001f\t\r\n\\\"/""#);
+    assert_eq!(json::escape_string("😀"), "\"😀\"");
+}
+
+#[test]
+fn to_string_stable() {
+    let a = object!{
+        "b" => array![object!{ y: 2, x: 1 }, "foo", 10],
+        "a" => array![array![3, 1], null]
+    };
+    let b = object!{
+        "a" => array![null, array![1, 3]],
+        "b" => array![10, object!{ x: 1, y: 2 }, "foo"]
+    };
+
+    assert_eq!(a.to_string_stable(), b.to_string_stable());
+    assert_eq!(a.to_string_stable(), r#"{"a":[[1,3],null],"b":["foo",10,{"x":1,"y":2}]}"#);
+    assert_ne!(a.dump(), b.dump());
+}
+
+#[test]
+fn dump_sorted_keys_numeric() {
+    let data = object!{
+        "10" => "ten",
+        "2" => "two",
+        "1" => array![object!{ "b" => 1, "a" => 2 }, 3, 1],
+        "007" => "bond",
+        "7" => "seven"
+    };
+
+    assert_eq!(
+        data.dump_sorted_keys(),
+        r#"{"1":[{"a":2,"b":1},3,1],"2":"two","007":"bond","7":"seven","10":"ten"}"#
+    );
+
+    // A single non-numeric key makes the whole object sort lexically
+    let mixed = object!{ "10" => 1, "2" => 2, "x" => 3 };
+
+    assert_eq!(mixed.dump_sorted_keys(), r#"{"10":1,"2":2,"x":3}"#);
+    assert_eq!(JsonValue::from("foo").dump_sorted_keys(), r#""foo""#);
+    assert_eq!(object!{}.dump_sorted_keys(), "{}");
+}
+
+#[test]
+fn sort_keys_recursive() {
+    let mut data = object!{
+        "zeta" => array![object!{ "b" => 1, "a" => object!{ "y" => json::Null, "x" => true } }, 2],
+        "alpha" => object!{ "10" => "ten", "9" => "nine" },
+        "mid" => "m"
+    };
+    let sorted = data.dump_sorted_keys();
+
+    data.sort_keys_recursive();
+
+    assert_eq!(data.dump(), sorted);
+    assert_eq!(
+        data.dump(),
+        r#"{"alpha":{"9":"nine","10":"ten"},"mid":"m","zeta":[{"a":{"x":true,"y":null},"b":1},2]}"#
+    );
+    assert_eq!(data["alpha"]["10"], "ten");
+}
+
+#[test]
+fn to_display_string() {
+    assert_eq!(JsonValue::from("foo \"bar\"").to_display_string(), "foo \"bar\"");
+    assert_eq!(JsonValue::from("a string that won't fit in a short").to_display_string(), "a string that won't fit in a short");
+    assert_eq!(JsonValue::from(2.5).to_display_string(), "2.5");
+    assert_eq!(JsonValue::from(-10).to_display_string(), "-10");
+    assert_eq!(JsonValue::from(true).to_display_string(), "true");
+    assert_eq!(Null.to_display_string(), "");
+    assert_eq!(array![1, "two", null].to_display_string(), r#"[1,"two",null]"#);
+    assert_eq!(object!{ a: { b: "c" } }.to_display_string(), r#"{"a":{"b":"c"}}"#);
+}
+
+#[test]
+fn write_ndjson() {
+    let values = vec![
+        object!{ event: "login", user: "foo" },
+        JsonValue::from("multi\nline\r\nstring"),
+        array![1, { nested: [true, null] }],
+    ];
+
+    let mut buf = Vec::new();
+
+    json::write_ndjson(&mut buf, values.clone()).unwrap();
+
+    let output = String::from_utf8(buf).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert!(output.ends_with('\n'));
+    assert_eq!(lines, vec![
+        r#"{"event":"login","user":"foo"}"#,
+        r#""multi\nline\r\nstring""#,
+        r#"[1,{"nested":[true,null]}]"#,
+    ]);
+
+    for (line, value) in lines.iter().zip(values) {
+        assert_eq!(parse(line).unwrap(), value);
+    }
+
+    let mut empty = Vec::new();
+
+    json::write_ndjson(&mut empty, Vec::new()).unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn to_html_safe_string() {
+    let data = object!{
+        "<key>": "</script><!-- a & b -->",
+        lines: "a\u{2028}b\u{2029}c",
+        plain: [1, true, null]
+    };
+
+    let safe = data.to_html_safe_string();
+
+    assert_eq!(safe, r#"{"\u003ckey\u003e":"\u003c/script\u003e\u003c!-- a \u0026 b --\u003e","lines":"a\u2028b\u2029c","plain":[1,true,null]}"#);
+    assert_eq!(json::parse(&safe).unwrap(), data);
+
+    assert_eq!(array!["safe"].to_html_safe_string(), array!["safe"].dump());
+}
+
+#[test]
+fn to_truncated_string() {
+    let data = object!{
+        items: [1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+        nested: [[1, 2, 3], "ünïcödé text"],
+        short: "ok"
+    };
+
+    assert_eq!(
+        data.to_truncated_string(3, 5),
+        r#"{"items":[1,2,3,…(+7 more)],"nested":[[1,2,3],"ünïcö…"],"short":"ok"}"#
+    );
+    assert_eq!(array![1, 2].to_truncated_string(0, 0), "[…(+2 more)]");
+    assert_eq!(data.to_truncated_string(10, 20), data.dump());
+}
+
+#[test]
+fn to_string_with_replacer() {
+    let data = object!{
+        id: 1,
+        _rev: "3-abc",
+        items: [{ name: "a", _internal: true }, 2, 3],
+        meta: { _hidden: { deep: 1 }, visible: "yes" }
+    };
+
+    let mut visited = Vec::new();
+
+    let dump = data.to_string_with_replacer(|key, value| {
+        visited.push(key.to_string());
+
+        if key.starts_with('_') || *value == 2 {
+            None
+        } else {
+            Some(value.clone())
+        }
+    });
+
+    assert_eq!(dump, r#"{"id":1,"items":[{"name":"a"},3],"meta":{"visible":"yes"}}"#);
+    assert_eq!(visited, ["", "id", "_rev", "items", "0", "name", "_internal", "1", "2", "meta", "_hidden", "visible"]);
+
+    // Replaced values are written out, and their members visited in turn
+    let dump = data.to_string_with_replacer(|key, value| match key {
+        "items" => Some(array![{ _secret: 1, ok: 2 }]),
+        "meta"  => None,
+        _       => Some(value.clone()),
+    });
+
+    assert_eq!(dump, r#"{"id":1,"_rev":"3-abc","items":[{"_secret":1,"ok":2}]}"#);
+    assert_eq!(data.to_string_with_replacer(|_, _| None), "null");
+}
+
+#[test]
+fn to_simple_xml() {
+    let data = object!{
+        "server" => object!{
+            "host" => "localhost",
+            "port" => 8080,
+            "tls" => false
+        },
+        "user" => array![
+            object!{ "name" => "<admin>", "roles" => array!["a", "b"] },
+            object!{ "name" => "guest", "roles" => array![] }
+        ],
+        "matrix" => array![array![1, 2], array![3]],
+        "note" => json::Null
+    };
+
+    assert_eq!(
+        data.to_simple_xml("config").unwrap(),
+        "<config>\
+            <server><host>localhost</host><port>8080</port><tls>false</tls></server>\
+            <user><name>&lt;admin&gt;</name><roles>a</roles><roles>b</roles></user>\
+            <user><name>guest</name></user>\
+            <matrix>1</matrix><matrix>2</matrix><matrix>3</matrix>\
+            <note/>\
+        </config>"
+    );
+
+    assert_eq!(json::from("a&b").to_simple_xml("v").unwrap(), "<v>a&amp;b</v>");
+    assert_eq!(json::Null.to_simple_xml("v").unwrap(), "<v/>");
+    assert_eq!(object!{}.to_simple_xml("v").unwrap(), "<v></v>");
+
+    assert_eq!(
+        array![1].to_simple_xml("v"),
+        Err(json::Error::wrong_type("Object or primitive at the root"))
+    );
+    assert_eq!(
+        object!{ "1st" => true }.to_simple_xml("v"),
+        Err(json::Error::invalid_argument(r#"Expected XML element name, got "1st""#))
+    );
+    assert!(object!{ "a b" => 1 }.to_simple_xml("v").is_err());
+    assert!(object!{ "ns:a" => 1 }.to_simple_xml("v").is_err());
+    assert!(object!{ "-a" => array![] }.to_simple_xml("v").is_err());
+    assert_eq!(json::from(1).to_simple_xml(""), Err(json::Error::invalid_argument(r#"Expected XML element name, got """#)));
+}
@@ -11,6 +11,21 @@ fn is_as_string() {
     assert_eq!(string.as_str().unwrap(), "foo");
 }
 
+#[test]
+fn as_enum() {
+    let allowed = ["active", "inactive"];
+
+    assert_eq!(JsonValue::from("active").as_enum(&allowed), Ok("active"));
+    assert_eq!(JsonValue::from("inactive").as_enum(&allowed), Ok("inactive"));
+    assert_eq!(
+        JsonValue::from("unknown").as_enum(&allowed),
+        Err(JsonError::wrong_type(r#"One of ["active", "inactive"], got "unknown""#))
+    );
+    assert!(JsonValue::from("Active").as_enum(&allowed).is_err());
+    assert!(JsonValue::from("").as_enum(&[]).is_err());
+    assert_eq!(JsonValue::from(1).as_enum(&allowed), Err(JsonError::wrong_type("String")));
+}
+
 #[test]
 fn is_as_number() {
     let number = JsonValue::from(42);
@@ -604,6 +619,35 @@ fn error_unexpected_token() {
     assert_eq!(format!("{}", err), "Unexpected character: ] at (4:3)");
 }
 
+#[test]
+fn error_context() {
+    use std::error::Error;
+
+    fn first_total(data: &JsonValue, key: &str) -> json::Result<f64> {
+        let first = data[key].first().map_err(|error| error.context("[0]"))?;
+
+        first.sum().map_err(|error| error.context(key))
+    }
+
+    let data = object!{ a: [[1, 2], 3], b: [[1, "2"]], c: [] };
+
+    assert_eq!(first_total(&data, "a"), Ok(3.0));
+
+    let err = first_total(&data, "b").unwrap_err();
+
+    assert_eq!(err, JsonError::Context {
+        path: "b".into(),
+        error: Box::new(JsonError::wrong_type("Number at index 1")),
+    });
+    assert_eq!(format!("{}", err), "b: Wrong type, expected: Number at index 1");
+    assert_eq!(err.source().unwrap().to_string(), "Wrong type, expected: Number at index 1");
+
+    let err = data["c"].first().map_err(|error| error.context("[0]")).unwrap_err().context("c").context("root");
+
+    assert_eq!(format!("{}", err), "root.c[0]: Array index 0 out of bounds for length 0");
+    assert!(JsonError::UnexpectedEndOfJson.source().is_none());
+}
+
 #[test]
 fn writer_generator() {
     let data = object!{
@@ -630,6 +674,25 @@ fn pretty_writer_generator() {
     assert_eq!(String::from_utf8(buf).unwrap(), "{\n    \"foo\": [\n        \"bar\",\n        100,\n        true\n    ]\n}");
 }
 
+#[test]
+fn pretty_writer_trailing_newline() {
+    let data = object!{
+        foo: [1]
+    };
+
+    let mut buf = Vec::new();
+
+    data.write_pretty_to(&mut buf, 2, true).expect("Can't fail with a Vec");
+
+    assert_eq!(String::from_utf8(buf).unwrap(), "{\n  \"foo\": [\n    1\n  ]\n}\n");
+
+    let mut buf = Vec::new();
+
+    data.write_pretty_to(&mut buf, 2, false).expect("Can't fail with a Vec");
+
+    assert_eq!(buf.last(), Some(&b'}'));
+}
+
 #[test]
 fn equality() {
     let left = object!{
@@ -667,3 +730,1499 @@ fn equality() {
     assert_ne!(left, change_string);
     assert_ne!(left, change_short);
 }
+
+#[test]
+fn from_query_string() {
+    let data = JsonValue::from_query_string("name=John%20Doe&tag=a&tag=b+c&empty");
+
+    assert_eq!(data, object!{
+        name: "John Doe",
+        tag: ["a", "b c"],
+        empty: ""
+    });
+}
+
+#[test]
+fn from_bracketed_form() {
+    let data = JsonValue::from_bracketed_form(&[
+        ("user[name]", "x"),
+        ("user[address][city]", "Paris"),
+        ("user[roles][]", "a"),
+        ("user[roles][]", "b"),
+        ("list[][id]", "1"),
+        ("list[][id]", "2"),
+        ("flag", "on"),
+        ("flag", "off"),
+    ]).unwrap();
+
+    assert_eq!(data, object!{
+        user: {
+            name: "x",
+            address: { city: "Paris" },
+            roles: ["a", "b"]
+        },
+        list: [{ id: "1" }, { id: "2" }],
+        flag: "off"
+    });
+
+    assert_eq!(JsonValue::from_bracketed_form(&[]), Ok(object!{}));
+
+    for key in &["", "[a]", "a[b", "a]", "a[b]c", "a[[b]]"] {
+        assert_eq!(
+            JsonValue::from_bracketed_form(&[(key, "1")]),
+            Err(JsonError::invalid_argument(&format!("Expected bracketed form key, got {:?}", key)))
+        );
+    }
+
+    let conflict = |key: &str, expected: &str| {
+        Err(JsonError::invalid_argument(&format!("Conflicting key {:?}, expected {}", key, expected)))
+    };
+
+    assert_eq!(JsonValue::from_bracketed_form(&[("a", "1"), ("a[b]", "2")]), conflict("a[b]", "Object"));
+    assert_eq!(JsonValue::from_bracketed_form(&[("a[]", "1"), ("a[b]", "2")]), conflict("a[b]", "Object"));
+    assert_eq!(JsonValue::from_bracketed_form(&[("a[b]", "1"), ("a[]", "2")]), conflict("a[]", "Array"));
+    assert_eq!(JsonValue::from_bracketed_form(&[("a[b]", "1"), ("a", "2")]), conflict("a", "String"));
+}
+
+#[test]
+fn to_query_string() {
+    let data = object!{
+        k: ["a", "b"],
+        flag: true,
+        name: "John Doe"
+    };
+
+    assert_eq!(data.to_query_string().unwrap(), "flag=true&k=a&k=b&name=John%20Doe");
+    assert_eq!(JsonValue::from_query_string(&data.to_query_string().unwrap()), object!{
+        k: ["a", "b"],
+        flag: "true",
+        name: "John Doe"
+    });
+
+    let nested = object!{ foo: { bar: 1 } };
+
+    assert_eq!(
+        nested.to_query_string(),
+        Err(JsonError::invalid_argument("Expected String, Number or Boolean for \"foo\""))
+    );
+    assert!(object!{ a: [1, null] }.to_query_string().is_err());
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut data = object!{ list: [] };
+
+    for i in 0..1000 {
+        data["list"].push(i).unwrap();
+    }
+
+    for _ in 0..990 {
+        data["list"].pop();
+    }
+
+    data["a"] = "foo".into();
+    data["key that does not fit in the inline buffer"] = 10.into();
+
+    data.shrink_to_fit();
+
+    match data["list"] {
+        JsonValue::Array(ref vec) => assert_eq!(vec.capacity(), 10),
+        _                         => panic!("Expected an array"),
+    }
+
+    // keys must survive the object store being reallocated
+    assert_eq!(data["a"], "foo");
+    assert_eq!(data["key that does not fit in the inline buffer"], 10);
+    assert_eq!(data.dump(), r#"{"list":[0,1,2,3,4,5,6,7,8,9],"a":"foo","key that does not fit in the inline buffer":10}"#);
+}
+
+#[test]
+fn eq_ignore_nulls() {
+    assert!(object!{ a: 1 }.eq_ignore_nulls(&object!{ a: 1, b: null }));
+    assert!(object!{ a: 1, b: null }.eq_ignore_nulls(&object!{ a: 1 }));
+    assert!(array![{ a: null }].eq_ignore_nulls(&array![{}]));
+
+    assert!(!object!{ a: 1 }.eq_ignore_nulls(&object!{ a: 1, b: false }));
+    assert!(!object!{ a: {} }.eq_ignore_nulls(&object!{}));
+    assert!(!array![1, null].eq_ignore_nulls(&array![1]));
+}
+
+#[test]
+fn estimated_size() {
+    use std::mem::size_of;
+
+    let mut list = JsonValue::Array(Vec::with_capacity(10));
+
+    for i in 0..10 {
+        list.push(i).unwrap();
+    }
+
+    assert!(list.estimated_size() >= 11 * size_of::<JsonValue>() + 16);
+    assert_eq!(list.estimated_size(), size_of::<JsonValue>() + (10 * size_of::<JsonValue>()).next_power_of_two() + 16);
+
+    let data = object!{
+        list: list.clone(),
+        text: "x".repeat(1000)
+    };
+
+    let size = data.estimated_size();
+    let minimum = size_of::<JsonValue>() + list.estimated_size() + 1024 + 16;
+
+    assert!(size >= minimum);
+    assert!(size < minimum + 1024);
+
+    // Long keys are allocated on their own
+    let key = "k".repeat(100);
+    let mut long = JsonValue::new_object();
+
+    long[key.as_str()] = 1.into();
+
+    let mut short = JsonValue::new_object();
+
+    short["k"] = 1.into();
+
+    assert_eq!(long.estimated_size(), short.estimated_size() + 128 + 16);
+}
+
+#[test]
+fn try_get_and_try_at() {
+    let data = object!{
+        foo: [null, { bar: 42 }]
+    };
+
+    assert_eq!(data.try_get("foo").and_then(|foo| foo.try_at(1)), Some(&object!{ bar: 42 }));
+    assert_eq!(data.try_get("foo").and_then(|foo| foo.try_at(0)), Some(&Null));
+    assert_eq!(data.try_get("foo").and_then(|foo| foo.try_at(2)), None);
+    assert_eq!(data.try_get("bar"), None);
+    assert_eq!(data.try_at(0), None);
+    assert_eq!(data["foo"].try_get("bar"), None);
+}
+
+#[test]
+fn object_builder() {
+    use json::object::ObjectBuilder;
+
+    let build = |admin: bool| {
+        ObjectBuilder::new()
+            .set("name", "foo")
+            .set(String::from("age"), 30)
+            .set_if(admin, "role", "admin")
+            .set_if(!admin, "role", Null)
+            .set("tags", array!["a"])
+            .set("name", "bar")
+            .build()
+    };
+
+    assert_eq!(build(true), object!{ name: "bar", age: 30, role: "admin", tags: ["a"] });
+    assert_eq!(build(false), object!{ name: "bar", age: 30, role: null, tags: ["a"] });
+    assert_eq!(ObjectBuilder::new().build(), JsonValue::new_object());
+}
+
+#[test]
+fn object_update() {
+    let mut data = object!{ counter: 1 };
+
+    data.update("counter", |counter| {
+        *counter = (counter.as_u64().unwrap() + 1).into();
+    }).unwrap();
+
+    assert_eq!(data, object!{ counter: 2 });
+    assert_eq!(data.update("missing", |_| {}), Err(json::Error::undefined("missing")));
+    assert_eq!(array![].update("counter", |_| {}), Err(json::Error::wrong_type("Object")));
+}
+
+#[test]
+fn array_concat() {
+    let data = json::array_concat(vec![array![1, 2], array!["foo"], array![null, true]]).unwrap();
+
+    assert_eq!(data, array![1, 2, "foo", null, true]);
+    assert_eq!(json::array_concat(vec![array![1], object!{}]), Err(json::Error::wrong_type("Array at index 1")));
+}
+
+#[test]
+fn pointer() {
+    let mut data = object!{
+        foo: [{ bar: 42 }, "baz"],
+        "a/b": 1,
+        "m~n": 2,
+        "": 3
+    };
+
+    assert_eq!(data.pointer("/foo/0/bar"), Some(&JsonValue::from(42)));
+    assert_eq!(data.pointer("/foo/1"), Some(&JsonValue::from("baz")));
+    assert_eq!(data.pointer("/a~1b"), Some(&JsonValue::from(1)));
+    assert_eq!(data.pointer("/m~0n"), Some(&JsonValue::from(2)));
+    assert_eq!(data.pointer("/"), Some(&JsonValue::from(3)));
+    assert_eq!(data.pointer("/foo/01"), None);
+    assert_eq!(data.pointer("/foo/2"), None);
+    assert_eq!(data.pointer("/foo/0/bar/baz"), None);
+    assert_eq!(data.pointer("foo"), None);
+
+    *data.pointer_mut("/foo/0/bar").unwrap() = 10.into();
+
+    assert_eq!(data["foo"][0]["bar"], 10);
+}
+
+#[test]
+fn replace_at() {
+    let mut data = object!{ foo: { bar: [1, 2, 3] } };
+
+    assert_eq!(data.replace_at("/foo/bar/1", object!{ x: 1 }).unwrap(), 2);
+    assert_eq!(data, object!{ foo: { bar: [1, { x: 1 }, 3] } });
+    assert_eq!(data.replace_at("/foo/baz", 1), Err(json::Error::undefined("/foo/baz")));
+
+    assert_eq!(data.replace_at("", "root").unwrap(), object!{ foo: { bar: [1, { x: 1 }, 3] } });
+    assert_eq!(data, "root");
+}
+
+#[test]
+fn redact() {
+    let mut data = object!{
+        user: { name: "Jane", ssn: "123-45-6789", cards: [{ number: 4111 }, { number: 5500 }] },
+        "a/b": 1
+    };
+
+    data.redact(&["/user/ssn", "/user/cards/1/number", "/a~1b", "/user/missing", "/user/cards/9"], "***").unwrap();
+
+    assert_eq!(data, object!{
+        user: { name: "Jane", ssn: "***", cards: [{ number: 4111 }, { number: "***" }] },
+        "a/b": "***"
+    });
+
+    assert_eq!(data.redact(&["/user/name", "user"], "***"), Err(json::Error::invalid_argument("Expected JSON Pointer, got \"user\"")));
+    assert_eq!(data["user"]["name"], "Jane");
+
+    data.redact(&[""], "gone").unwrap();
+    assert_eq!(data, "gone");
+}
+
+#[test]
+fn array_chunks() {
+    let data = array![1, 2, 3, 4, 5, 6, 7];
+
+    assert_eq!(data.chunks(3).unwrap(), array![[1, 2, 3], [4, 5, 6], [7]]);
+    assert_eq!(data.chunks(7).unwrap(), array![[1, 2, 3, 4, 5, 6, 7]]);
+    assert_eq!(array![].chunks(3).unwrap(), array![]);
+    assert_eq!(data.chunks(0), Err(JsonError::invalid_argument("Chunk size must be greater than 0")));
+    assert_eq!(data.chunks(0).unwrap_err().to_string(), "Invalid argument: Chunk size must be greater than 0");
+    assert_eq!(object!{}.chunks(3), Err(JsonError::wrong_type("Array")));
+}
+
+#[test]
+fn array_windows() {
+    let data = array![1, 2, 3, 4, 5];
+
+    assert_eq!(data.windows(3).unwrap(), array![[1, 2, 3], [2, 3, 4], [3, 4, 5]]);
+    assert_eq!(data.windows(1).unwrap(), array![[1], [2], [3], [4], [5]]);
+    assert_eq!(data.windows(5).unwrap(), array![[1, 2, 3, 4, 5]]);
+    assert_eq!(data.windows(6).unwrap(), array![]);
+    assert_eq!(data.windows(0), Err(JsonError::invalid_argument("Window size must be greater than 0")));
+    assert_eq!(Null.windows(2), Err(JsonError::wrong_type("Array")));
+}
+
+#[test]
+fn from_string_like() {
+    use std::borrow::Cow;
+
+    let long = "a string that is too long to fit in a short";
+
+    assert_eq!(JsonValue::from("foo"), "foo");
+    assert_eq!(JsonValue::from(String::from("foo")), "foo");
+    assert_eq!(JsonValue::from(Cow::Borrowed("foo")), "foo");
+    assert_eq!(JsonValue::from(Cow::Owned::<str>(String::from("foo"))), "foo");
+    assert_eq!(JsonValue::from(Cow::Borrowed(long)), long);
+    assert!(JsonValue::from(Cow::Borrowed("foo")).is_string());
+}
+
+#[test]
+fn as_array_lenient() {
+    assert_eq!(Null.as_array_lenient(), array![]);
+    assert_eq!(JsonValue::from("foo").as_array_lenient(), array!["foo"]);
+    assert_eq!(JsonValue::from(42).as_array_lenient(), array![42]);
+    assert_eq!(JsonValue::from(false).as_array_lenient(), array![false]);
+    assert_eq!(object!{ a: 1 }.as_array_lenient(), array![{ a: 1 }]);
+    assert_eq!(array![].as_array_lenient(), array![]);
+    assert_eq!(array![{ a: 1 }, null].as_array_lenient(), array![{ a: 1 }, null]);
+}
+
+#[test]
+fn diff_keys() {
+    let old = object!{ same: 1, removed: true, changed: [1, 2], nested: { a: 1 } };
+    let new = object!{ added: null, nested: { a: 2 }, changed: [1, 2, 3], same: 1 };
+
+    let (added, removed, changed) = old.diff_keys(&new).unwrap();
+
+    assert_eq!(added, ["added"]);
+    assert_eq!(removed, ["removed"]);
+    assert_eq!(changed, ["changed", "nested"]);
+
+    let empty: Vec<String> = Vec::new();
+
+    assert_eq!(old.diff_keys(&old).unwrap(), (empty.clone(), empty.clone(), empty));
+    assert_eq!(old.diff_keys(&array![]), Err(JsonError::wrong_type("Object")));
+    assert_eq!(Null.diff_keys(&old), Err(JsonError::wrong_type("Object")));
+}
+
+#[test]
+fn as_finite_number() {
+    use std::f64;
+
+    assert_eq!(JsonValue::from(-12.5).as_finite_number(), Ok(-12.5));
+    assert_eq!(JsonValue::from(f64::MAX).as_finite_number(), Ok(f64::MAX));
+    assert_eq!(parse("[1e2]").unwrap()[0].as_finite_number(), Ok(100.0));
+    assert_eq!(JsonValue::from(f64::NAN).as_finite_number(), Err(JsonError::NonFiniteNumber));
+    assert_eq!(JsonValue::from(f64::INFINITY).as_finite_number(), Err(JsonError::NonFiniteNumber));
+    assert_eq!(JsonValue::from(f64::NEG_INFINITY).as_finite_number(), Err(JsonError::NonFiniteNumber));
+    assert_eq!(parse("-1e999").unwrap().as_finite_number(), Err(JsonError::NonFiniteNumber));
+    assert_eq!(JsonValue::from("1").as_finite_number(), Err(JsonError::wrong_type("Number")));
+    assert_eq!(Null.as_finite_number(), Err(JsonError::wrong_type("Number")));
+}
+
+#[test]
+fn as_integer_string() {
+    // Parsed numbers keep their exact mantissa, while `f64` can't
+    let id = parse("9007199254740993").unwrap();
+
+    assert_eq!(id.as_integer_string(), Ok("9007199254740993".into()));
+    assert_eq!(id.as_f64(), Some(9007199254740992.0));
+    assert_eq!(JsonValue::from(9007199254740993u64).as_integer_string(), Ok("9007199254740993".into()));
+    assert_eq!(JsonValue::from(9007199254740993.0).as_integer_string(), Ok("9007199254740992".into()));
+
+    assert_eq!(JsonValue::from(u64::MAX).as_integer_string(), Ok(u64::MAX.to_string()));
+    assert_eq!(JsonValue::from(i64::MIN).as_integer_string(), Ok(i64::MIN.to_string()));
+    assert_eq!(parse("1e3").unwrap().as_integer_string(), Ok("1000".into()));
+    assert_eq!(parse("-12.00").unwrap().as_integer_string(), Ok("-12".into()));
+    assert_eq!(parse("-0").unwrap().as_integer_string(), Ok("0".into()));
+    assert_eq!(parse("0e400").unwrap().as_integer_string(), Ok("0".into()));
+    assert_eq!(json::parse_preserve_numbers("12345678901234567").unwrap().as_integer_string(), Ok("12345678901234567".into()));
+
+    assert_eq!(JsonValue::from(1.5).as_integer_string(), Err(JsonError::IntegerOutOfRange("1.5".into())));
+    assert!(parse("18446744073709551620").unwrap().as_integer_string().is_err());
+    assert!(parse("-9223372036854775809").unwrap().as_integer_string().is_err());
+    assert!(parse("1e20").unwrap().as_integer_string().is_err());
+    assert!(JsonValue::from(f64::NAN).as_integer_string().is_err());
+    assert_eq!(JsonValue::from("1").as_integer_string(), Err(JsonError::wrong_type("Number")));
+}
+
+#[test]
+fn as_checked_integers() {
+    use std::{ f64, i32, u32 };
+
+    assert_eq!(JsonValue::from(i32::MAX).as_i32_checked(), Ok(i32::MAX));
+    assert_eq!(JsonValue::from(i32::MIN).as_i32_checked(), Ok(i32::MIN));
+    assert_eq!(
+        JsonValue::from(i32::MAX as i64 + 1).as_i32_checked(),
+        Err(JsonError::IntegerOutOfRange("2147483648".into()))
+    );
+    assert_eq!(
+        JsonValue::from(i32::MIN as i64 - 1).as_i32_checked(),
+        Err(JsonError::IntegerOutOfRange("-2147483649".into()))
+    );
+
+    assert_eq!(JsonValue::from(u32::MAX).as_u32_checked(), Ok(u32::MAX));
+    assert_eq!(JsonValue::from(u32::MAX as u64 + 1).as_u32_checked(), Err(JsonError::IntegerOutOfRange("4294967296".into())));
+    assert_eq!(JsonValue::from(-1).as_u32_checked(), Err(JsonError::IntegerOutOfRange("-1".into())));
+    assert_eq!(JsonValue::from(-0.0).as_u32_checked(), Ok(0));
+
+    assert_eq!(parse("10.0").unwrap().as_i32_checked(), Ok(10));
+    assert_eq!(parse("-2e3").unwrap().as_i32_checked(), Ok(-2000));
+    assert_eq!(JsonValue::from(2.5).as_i32_checked(), Err(JsonError::IntegerOutOfRange("2.5".into())));
+    assert_eq!(JsonValue::from(f64::NAN).as_i32_checked(), Err(JsonError::NonFiniteNumber));
+    assert_eq!(JsonValue::from(f64::NAN).as_u32_checked(), Err(JsonError::NonFiniteNumber));
+    assert_eq!(JsonValue::from("1").as_u32_checked(), Err(JsonError::wrong_type("Number")));
+}
+
+#[test]
+fn coerce_bool() {
+    assert_eq!(JsonValue::from(true).coerce_bool(), Ok(true));
+    assert_eq!(JsonValue::from(false).coerce_bool(), Ok(false));
+    assert_eq!(JsonValue::from(1).coerce_bool(), Ok(true));
+    assert_eq!(JsonValue::from(-0.5).coerce_bool(), Ok(true));
+    assert_eq!(JsonValue::from(0).coerce_bool(), Ok(false));
+    assert_eq!(JsonValue::from(std::f64::NAN).coerce_bool(), Err(JsonError::NonFiniteNumber));
+
+    for truthy in &["true", "TRUE", "1", "yes", "Yes"] {
+        assert_eq!(JsonValue::from(*truthy).coerce_bool(), Ok(true));
+    }
+
+    for falsy in &["false", "False", "0", "no", "NO"] {
+        assert_eq!(JsonValue::from(*falsy).coerce_bool(), Ok(false));
+    }
+
+    assert_eq!(JsonValue::from("on").coerce_bool(), Err(JsonError::wrong_type("Boolean")));
+    assert_eq!(JsonValue::from("").coerce_bool(), Err(JsonError::wrong_type("Boolean")));
+    assert_eq!(Null.coerce_bool(), Err(JsonError::wrong_type("Boolean")));
+    assert_eq!(array![true].coerce_bool(), Err(JsonError::wrong_type("Boolean")));
+}
+
+#[test]
+fn array_first_last() {
+    let data = array![1, 2, 3];
+
+    assert_eq!(data.first(), Ok(&JsonValue::from(1)));
+    assert_eq!(data.last(), Ok(&JsonValue::from(3)));
+    assert_eq!(array!["only"].first(), array!["only"].last());
+    assert_eq!(array![].first(), Err(JsonError::ArrayIndexOutOfBounds { index: 0, len: 0 }));
+    assert_eq!(array![].last(), Err(JsonError::ArrayIndexOutOfBounds { index: 0, len: 0 }));
+    assert_eq!(object!{}.first(), Err(JsonError::wrong_type("Array")));
+    assert_eq!(Null.last(), Err(JsonError::wrong_type("Array")));
+}
+
+#[test]
+fn array_swap() {
+    let mut data = array![1, 2, 3];
+
+    data.swap(0, 1).unwrap();
+    assert_eq!(data, array![2, 1, 3]);
+
+    data.swap(2, 2).unwrap();
+    assert_eq!(data, array![2, 1, 3]);
+
+    assert_eq!(data.swap(1, 3), Err(JsonError::ArrayIndexOutOfBounds { index: 3, len: 3 }));
+    assert_eq!(data.swap(5, 0), Err(JsonError::ArrayIndexOutOfBounds { index: 5, len: 3 }));
+    assert_eq!(data, array![2, 1, 3]);
+    assert_eq!(object!{}.swap(0, 0), Err(JsonError::wrong_type("Array")));
+}
+
+#[test]
+fn array_rotate() {
+    let mut data = array![1, 2, 3, 4, 5];
+
+    data.rotate_left(2).unwrap();
+    assert_eq!(data, array![3, 4, 5, 1, 2]);
+
+    data.rotate_right(2).unwrap();
+    assert_eq!(data, array![1, 2, 3, 4, 5]);
+
+    data.rotate_left(12).unwrap();
+    assert_eq!(data, array![3, 4, 5, 1, 2]);
+
+    data.rotate_right(5).unwrap();
+    assert_eq!(data, array![3, 4, 5, 1, 2]);
+
+    let mut empty = array![];
+
+    assert!(empty.rotate_left(3).is_ok());
+    assert!(empty.rotate_right(3).is_ok());
+    assert_eq!(empty, array![]);
+    assert_eq!(object!{}.rotate_left(1), Err(JsonError::wrong_type("Array")));
+    assert_eq!(Null.rotate_right(1), Err(JsonError::wrong_type("Array")));
+}
+
+#[test]
+fn array_swap_remove() {
+    let mut data = array![1, 2, 3];
+
+    assert_eq!(data.swap_remove(0), Ok(JsonValue::from(1)));
+    assert_eq!(data, array![3, 2]);
+    assert_eq!(data.swap_remove(1), Ok(JsonValue::from(2)));
+    assert_eq!(data, array![3]);
+    assert_eq!(data.swap_remove(1), Err(JsonError::ArrayIndexOutOfBounds { index: 1, len: 1 }));
+    assert_eq!(Null.swap_remove(0), Err(JsonError::wrong_type("Array")));
+}
+
+#[test]
+fn array_binary_search() {
+    let data = array![-2, 0.5, 3, 10];
+
+    assert_eq!(data.binary_search(&JsonValue::from(3)), Ok(Ok(2)));
+    assert_eq!(data.binary_search(&JsonValue::from(-5)), Ok(Err(0)));
+    assert_eq!(data.binary_search(&JsonValue::from(4)), Ok(Err(3)));
+    assert_eq!(data.binary_search(&JsonValue::from(11)), Ok(Err(4)));
+    assert_eq!(array![].binary_search(&JsonValue::from(1)), Ok(Err(0)));
+    assert_eq!(object!{}.binary_search(&JsonValue::from(1)), Err(JsonError::wrong_type("Array")));
+}
+
+#[test]
+fn leaves() {
+    let data = object!{
+        name: "foo",
+        tags: ["a", "b"],
+        nested: {
+            "a/b": { "m~n": null },
+            empty_array: [],
+            empty_object: {}
+        },
+        "": 1
+    };
+
+    let leaves = data.leaves();
+
+    assert_eq!(leaves, vec![
+        ("/name".to_string(), &JsonValue::from("foo")),
+        ("/tags/0".to_string(), &JsonValue::from("a")),
+        ("/tags/1".to_string(), &JsonValue::from("b")),
+        ("/nested/a~1b/m~0n".to_string(), &Null),
+        ("/nested/empty_array".to_string(), &array![]),
+        ("/nested/empty_object".to_string(), &object!{}),
+        ("/".to_string(), &JsonValue::from(1)),
+    ]);
+
+    for (pointer, value) in leaves {
+        assert_eq!(data.pointer(&pointer), Some(value));
+    }
+
+    assert_eq!(JsonValue::from(42).leaves(), vec![(String::new(), &JsonValue::from(42))]);
+}
+
+#[test]
+fn unflatten() {
+    let data = object!{
+        name: "foo",
+        tags: ["a", { b: [1, 2] }],
+        nested: {
+            "a/b": { "m~n": null },
+            empty_array: [],
+            empty_object: {}
+        }
+    };
+
+    let pairs = data.leaves().into_iter().map(|(pointer, value)| (pointer, value.clone()));
+
+    assert_eq!(JsonValue::unflatten(pairs).unwrap(), data);
+
+    let appended = JsonValue::unflatten(vec![
+        ("/a/0".to_string(), JsonValue::from(1)),
+        ("/a/1/b".to_string(), JsonValue::from(2)),
+        ("/a/0".to_string(), JsonValue::from(3)),
+    ]).unwrap();
+
+    assert_eq!(appended, object!{ a: [3, { b: 2 }] });
+    assert_eq!(JsonValue::unflatten(vec![(String::new(), JsonValue::from(1))]).unwrap(), 1);
+    assert_eq!(JsonValue::unflatten(Vec::new()).unwrap(), Null);
+}
+
+#[test]
+fn unflatten_conflicts() {
+    let array_then_key = vec![
+        ("/a/0".to_string(), JsonValue::from(1)),
+        ("/a/b".to_string(), JsonValue::from(2)),
+    ];
+    let scalar_then_nested = vec![
+        ("/a/b".to_string(), JsonValue::from(1)),
+        ("/a/b/c".to_string(), JsonValue::from(2)),
+    ];
+
+    assert_eq!(JsonValue::unflatten(array_then_key), Err(JsonError::wrong_type(r#"Object at "/a""#)));
+    assert_eq!(JsonValue::unflatten(scalar_then_nested), Err(JsonError::wrong_type(r#"Object or Array at "/a/b""#)));
+    assert_eq!(
+        JsonValue::unflatten(vec![("a".to_string(), Null)]),
+        Err(JsonError::invalid_argument(r#"Expected JSON Pointer, got "a""#))
+    );
+
+    // Indexes can't skip past the end of an array
+    assert_eq!(
+        JsonValue::unflatten(vec![("/a/2".to_string(), Null)]),
+        Err(JsonError::ArrayIndexOutOfBounds { index: 2, len: 0 })
+    );
+    assert_eq!(
+        JsonValue::unflatten(vec![("/a/18446744073709551615".to_string(), Null)]),
+        Err(JsonError::ArrayIndexOutOfBounds { index: usize::MAX, len: 0 })
+    );
+    assert_eq!(
+        JsonValue::unflatten(vec![("/0".to_string(), Null), ("/4000000000".to_string(), Null)]),
+        Err(JsonError::ArrayIndexOutOfBounds { index: 4000000000, len: 1 })
+    );
+}
+
+#[test]
+fn get_ci() {
+    let data = object!{
+        userid: 1,
+        "userId" => 2,
+        name: "foo"
+    };
+
+    assert_eq!(data.get_ci("UserID"), Ok(&JsonValue::from(2)));
+    assert_eq!(data.get_ci("NAME"), Ok(&JsonValue::from("foo")));
+    assert_eq!(data.get_ci("user_id"), Err(JsonError::undefined("user_id")));
+    assert_eq!(array![].get_ci("name"), Err(JsonError::wrong_type("Object")));
+}
+
+#[test]
+fn as_result() {
+    let success = object!{ ok: true, data: [1, 2] };
+
+    assert_eq!(success.as_result("ok", "data", "error"), Ok(&array![1, 2]));
+    assert_eq!(object!{ ok: true }.as_result("ok", "data", "error"), Ok(&Null));
+
+    let failure = object!{ success: false, message: "Rate limited", data: null };
+
+    assert_eq!(failure.as_result("success", "data", "message"), Err(JsonError::ErrorResponse("Rate limited".into())));
+    assert_eq!(
+        object!{ ok: false, error: { code: 404 } }.as_result("ok", "data", "error"),
+        Err(JsonError::ErrorResponse(r#"{"code":404}"#.into()))
+    );
+    assert_eq!(object!{ ok: false }.as_result("ok", "data", "error"), Err(JsonError::ErrorResponse("".into())));
+
+    assert_eq!(object!{ ok: 1 }.as_result("ok", "data", "error"), Err(JsonError::wrong_type("Boolean at \"ok\"")));
+    assert_eq!(object!{}.as_result("ok", "data", "error"), Err(JsonError::undefined("ok")));
+    assert_eq!(array![].as_result("ok", "data", "error"), Err(JsonError::wrong_type("Object")));
+    assert_eq!(
+        failure.as_result("success", "data", "message").unwrap_err().to_string(),
+        "Error response: Rate limited"
+    );
+}
+
+#[test]
+fn get_many() {
+    let data = object!{ a: 1, b: "two", c: [3], d: null };
+
+    assert_eq!(data.get_many(&["c", "a", "d"]), Ok(vec![&array![3], &JsonValue::from(1), &Null]));
+    assert_eq!(data.get_many(&[]), Ok(vec![]));
+    assert_eq!(data.get_many(&["a", "x", "y"]), Err(JsonError::undefined("x")));
+    assert_eq!(array![].get_many(&["a"]), Err(JsonError::wrong_type("Object")));
+}
+
+#[test]
+fn retain_keys() {
+    let mut data = object!{
+        id: 1,
+        secret: "foo",
+        name: { first: "Jane" },
+        internal: true
+    };
+
+    data.retain_keys(&["name", "id", "missing"]).unwrap();
+
+    assert_eq!(data, object!{ id: 1, name: { first: "Jane" } });
+    assert_eq!(data.dump(), r#"{"id":1,"name":{"first":"Jane"}}"#);
+
+    data.retain_keys(&[]).unwrap();
+
+    assert_eq!(data, object!{});
+    assert_eq!(array![].retain_keys(&["id"]), Err(JsonError::wrong_type("Object")));
+}
+
+#[test]
+fn extract_prefix() {
+    let config = object!{
+        db_host: "localhost",
+        web_port: 80,
+        db_port: 5432,
+        db_: "empty",
+        db: "no underscore",
+        db_pool: { size: 4 }
+    };
+
+    assert_eq!(config.extract_prefix("db_").unwrap(), object!{
+        host: "localhost",
+        port: 5432,
+        "": "empty",
+        pool: { size: 4 }
+    });
+    assert_eq!(config.extract_prefix("web_").unwrap(), object!{ port: 80 });
+    assert_eq!(config.extract_prefix("cache_").unwrap(), object!{});
+    assert_eq!(config.extract_prefix("").unwrap(), config);
+    assert_eq!(array![].extract_prefix("db_"), Err(JsonError::wrong_type("Object")));
+}
+
+#[test]
+fn rename_keys_with() {
+    let mut data = object!{
+        user_id: 1,
+        first_name: "foo",
+        nested_value: { inner_key: true },
+        id: 2
+    };
+
+    data.rename_keys_with(json::snake_to_camel_case).unwrap();
+
+    assert_eq!(data, object!{
+        userId: 1,
+        firstName: "foo",
+        nestedValue: { inner_key: true },
+        id: 2
+    });
+    assert_eq!(data.entries().map(|(key, _)| key).collect::<Vec<_>>(), ["userId", "firstName", "nestedValue", "id"]);
+
+    data.rename_keys_with(json::camel_to_snake_case).unwrap();
+
+    assert_eq!(data["first_name"], "foo");
+
+    // Last one wins
+    let mut data = object!{ a: 1, b: 2, c: 3 };
+
+    data.rename_keys_with(|key: &str| if key == "c" { key.to_string() } else { "x".to_string() }).unwrap();
+
+    assert_eq!(data, object!{ x: 2, c: 3 });
+    assert_eq!(array![].rename_keys_with(str::to_uppercase), Err(JsonError::wrong_type("Object")));
+}
+
+#[test]
+fn push_checked() {
+    let mut data = array![];
+
+    for i in 0..4 {
+        data.push_checked(i, 5).unwrap();
+    }
+
+    assert_eq!(data.node_count(), 5);
+    assert_eq!(data.push_checked(4, 5), Err(JsonError::ExceededNodeLimit(5)));
+    assert_eq!(data, array![0, 1, 2, 3]);
+
+    // Nested values count with all their nodes
+    let mut data = array![];
+
+    assert_eq!(data.push_checked(array![1, [2]], 3), Err(JsonError::ExceededNodeLimit(3)));
+    assert!(data.push_checked(array![1, [2]], 5).is_ok());
+    assert_eq!(data.node_count(), 5);
+    assert_eq!(object!{}.push_checked(1, 10), Err(JsonError::wrong_type("Array")));
+}
+
+#[test]
+fn depth() {
+    let data = object!{ a: [1, { b: [] }], c: "d" };
+
+    assert_eq!(data.depth(), 4);
+    assert_eq!(data.count_nodes(), 6);
+    assert_eq!(JsonValue::from(1).count_nodes(), 1);
+    assert_eq!(JsonValue::from(1).depth(), 0);
+    assert_eq!(array![].depth(), 1);
+    assert_eq!(array![[], 1].depth(), 2);
+}
+
+#[test]
+fn insert_checked() {
+    let mut data = object!{ a: [1, 2] };
+
+    assert_eq!(data.node_count(), 4);
+    assert_eq!(data.insert_checked("b", 1, 4), Err(JsonError::ExceededNodeLimit(4)));
+    assert!(data.insert_checked("b", 1, 5).is_ok());
+
+    // Replacing `a` frees up its nodes
+    assert!(data.insert_checked("a", array![1, 2, 3], 6).is_ok());
+    assert_eq!(data, object!{ a: [1, 2, 3], b: 1 });
+    assert_eq!(data.insert_checked("c", Null, 6), Err(JsonError::ExceededNodeLimit(6)));
+    assert_eq!(array![].insert_checked("a", 1, 10), Err(JsonError::wrong_type("Object")));
+}
+
+#[test]
+fn has_typed_key() {
+    let data = object!{ name: "Jane", age: 30, admin: false, tags: [], address: {}, email: null };
+
+    assert!(data.has_string("name"));
+    assert!(data.has_number("age"));
+    assert!(data.has_boolean("admin"));
+    assert!(data.has_array("tags"));
+    assert!(data.has_object("address"));
+
+    assert!(!data.has_number("name"));
+    assert!(!data.has_string("age"));
+    assert!(!data.has_object("tags"));
+    assert!(!data.has_string("email"));
+    assert!(!data.has_string("missing"));
+    assert!(!array!["name"].has_string("name"));
+}
+
+#[test]
+fn merge_with() {
+    use json::ArrayMergeStrategy::*;
+
+    let base = object!{ list: [{ a: 1, b: 2 }, 2], nested: { keep: true, value: 1 } };
+    let layer = object!{ list: [{ a: 10 }, 20, 30], nested: { value: 2 }, extra: "x" };
+
+    let mut data = base.clone();
+    data.merge_with(layer.clone(), Replace).unwrap();
+    assert_eq!(data, object!{ list: [{ a: 10 }, 20, 30], nested: { keep: true, value: 2 }, extra: "x" });
+
+    let mut data = base.clone();
+    data.merge_with(layer.clone(), Concat).unwrap();
+    assert_eq!(data, object!{ list: [{ a: 1, b: 2 }, 2, { a: 10 }, 20, 30], nested: { keep: true, value: 2 }, extra: "x" });
+
+    let mut data = base.clone();
+    data.merge_with(layer.clone(), ByIndex).unwrap();
+    assert_eq!(data, object!{ list: [{ a: 10, b: 2 }, 20, 30], nested: { keep: true, value: 2 }, extra: "x" });
+
+    // Shorter array merged in by index keeps the remaining members
+    let mut data = object!{ list: [1, 2, 3] };
+    data.merge_with(object!{ list: [10] }, ByIndex).unwrap();
+    assert_eq!(data, object!{ list: [10, 2, 3] });
+
+    // Mismatched types are replaced
+    let mut data = object!{ a: [1], b: { c: 1 } };
+    data.merge_with(object!{ a: { x: 1 }, b: null }, Concat).unwrap();
+    assert_eq!(data, object!{ a: { x: 1 }, b: null });
+
+    let mut data = array![1];
+    assert_eq!(data.merge_with(object!{}, Concat), Err(json::Error::wrong_type("Object")));
+    assert_eq!(base.clone().merge_with(array![1], Concat), Err(json::Error::wrong_type("Object")));
+}
+
+#[test]
+fn partition() {
+    let data = array![1, 2, 3, 4, 5, 6];
+
+    let (even, odd) = data.partition(|n| n.as_u32().unwrap() % 2 == 0).unwrap();
+
+    assert_eq!(even, array![2, 4, 6]);
+    assert_eq!(odd, array![1, 3, 5]);
+    assert_eq!(data, array![1, 2, 3, 4, 5, 6]);
+
+    assert_eq!(array![].partition(|_| true).unwrap(), (array![], array![]));
+    assert_eq!(object!{}.partition(|_| true), Err(json::Error::wrong_type("Array")));
+}
+
+#[test]
+fn flat_map_array() {
+    let data = array![1, 2, 3];
+
+    assert_eq!(data.flat_map_array(|n| array![n.clone(), n.clone()]).unwrap(), array![1, 1, 2, 2, 3, 3]);
+    assert_eq!(data.flat_map_array(|n| n.clone()).unwrap(), data);
+
+    // Empty arrays drop members, nested arrays are only spliced one level
+    let mapped = data.flat_map_array(|n| match n.as_u32() {
+        Some(1) => array![],
+        Some(2) => array![[2, 2]],
+        _       => object!{ n: n.clone() },
+    }).unwrap();
+
+    assert_eq!(mapped, array![[2, 2], { n: 3 }]);
+    assert_eq!(array![].flat_map_array(|_| array![1]).unwrap(), array![]);
+    assert_eq!(object!{}.flat_map_array(|n| n.clone()), Err(json::Error::wrong_type("Array")));
+}
+
+#[test]
+fn from_csv_record() {
+    let headers = ["id", "name", "score"];
+
+    let data = JsonValue::from_csv_record(&headers, &["7", "Jane", ""]).unwrap();
+
+    assert_eq!(data, object!{ id: "7", name: "Jane", score: "" });
+    assert_eq!(data.entries().map(|(key, _)| key).collect::<Vec<_>>(), headers);
+
+    assert_eq!(
+        JsonValue::from_csv_record(&headers, &["7", "Jane"]),
+        Err(json::Error::invalid_argument("Record of 3 fields, got 2"))
+    );
+}
+
+#[test]
+fn to_csv_rows() {
+    let data = array![
+        { name: "Jane", age: 30, tags: ["a", "b"] },
+        { name: "John", active: true, email: null }
+    ];
+
+    let (headers, rows) = data.to_csv_rows().unwrap();
+
+    assert_eq!(headers, ["active", "age", "email", "name", "tags"]);
+    assert_eq!(rows, [
+        ["", "30", "", "Jane", r#"["a","b"]"#],
+        ["true", "", "", "John", ""],
+    ]);
+
+    assert_eq!(array![].to_csv_rows().unwrap(), (vec![], vec![]));
+    assert_eq!(array![{ a: 1 }, 2].to_csv_rows(), Err(json::Error::wrong_type("Array of objects")));
+    assert_eq!(object!{}.to_csv_rows(), Err(json::Error::wrong_type("Array")));
+}
+
+#[test]
+fn content_hash() {
+    let a = object!{ id: 1, user: { name: "Jane", roles: ["admin", "dev"] } };
+    let b = object!{ user: { roles: ["admin", "dev"], name: "Jane" }, id: 1 };
+
+    assert_eq!(a.content_hash(), b.content_hash());
+    assert_eq!(a.content_hash(), json::parse(&b.pretty(2)).unwrap().content_hash());
+
+    // Array order is significant
+    let c = object!{ id: 1, user: { name: "Jane", roles: ["dev", "admin"] } };
+
+    assert_ne!(a.content_hash(), c.content_hash());
+    assert_ne!(json::from(1).content_hash(), json::from("1").content_hash());
+
+    // Equal numbers hash the same however they are written
+    let d = json::parse("[1.0,-0,1e2]").unwrap();
+
+    assert_eq!(d.content_hash(), json::parse("[1,0,100]").unwrap().content_hash());
+    assert_ne!(d.content_hash(), json::parse("[1,0,10]").unwrap().content_hash());
+    assert_eq!(d.content_hash(), json::parse_preserve_numbers("[1.0,-0,1e2]").unwrap().content_hash());
+}
+
+#[test]
+fn apply_defaults() {
+    let mut config = object!{
+        name: "app",
+        timeout: null,
+        plugins: ["a"],
+        db: { host: "example.com", pool: { max: 20 } }
+    };
+
+    let defaults = object!{
+        name: "default",
+        timeout: 30,
+        plugins: ["b", "c"],
+        debug: false,
+        db: { host: "localhost", port: 5432, pool: { min: 1, max: 10 } }
+    };
+
+    config.apply_defaults(&defaults).unwrap();
+
+    assert_eq!(config, object!{
+        name: "app",
+        timeout: null,
+        plugins: ["a"],
+        db: { host: "example.com", pool: { max: 20, min: 1 }, port: 5432 },
+        debug: false
+    });
+
+    assert_eq!(config.apply_defaults(&array![]), Err(json::Error::wrong_type("Object")));
+    assert_eq!(array![].apply_defaults(&defaults), Err(json::Error::wrong_type("Object")));
+}
+
+#[test]
+fn as_pair_and_triple() {
+    let point = array![1, "two"];
+    let color = array![255, 128, 0];
+
+    assert_eq!(point.as_pair().unwrap(), (&json::from(1), &json::from("two")));
+    assert_eq!(color.as_triple().unwrap(), (&json::from(255), &json::from(128), &json::from(0)));
+
+    assert_eq!(color.as_pair(), Err(json::Error::wrong_type("Array of length 2")));
+    assert_eq!(point.as_triple(), Err(json::Error::wrong_type("Array of length 3")));
+    assert_eq!(array![].as_pair(), Err(json::Error::wrong_type("Array of length 2")));
+    assert_eq!(object!{}.as_pair(), Err(json::Error::wrong_type("Array")));
+}
+
+#[test]
+fn array_at() {
+    let mut data = object!{ existing: ["a"], empty: null, name: "foo" };
+
+    for tag in &["b", "c"] {
+        data.array_at("existing").unwrap().push(*tag).unwrap();
+        data.array_at("fresh").unwrap().push(*tag).unwrap();
+    }
+
+    data.array_at("empty").unwrap();
+
+    assert_eq!(data, object!{
+        existing: ["a", "b", "c"],
+        empty: [],
+        name: "foo",
+        fresh: ["b", "c"]
+    });
+
+    assert_eq!(data.array_at("name"), Err(JsonError::wrong_type("Array at \"name\"")));
+    assert_eq!(array![].array_at("items"), Err(JsonError::wrong_type("Object")));
+}
+
+#[test]
+fn compact_nulls() {
+    let mut data = object!{
+        id: 1,
+        name: null,
+        meta: {
+            tag: null,
+            owner: { email: null, login: "foo" }
+        },
+        list: [null, { gone: null }, 2]
+    };
+
+    let mut dropped = data.clone();
+
+    data.compact(false);
+
+    assert_eq!(data, object!{
+        id: 1,
+        meta: {
+            owner: { login: "foo" }
+        },
+        list: [null, {}, 2]
+    });
+
+    dropped.compact(true);
+
+    assert_eq!(dropped["list"], array![{}, 2]);
+
+    let mut value = JsonValue::Null;
+    value.compact(true);
+
+    assert!(value.is_null());
+}
+
+#[test]
+fn normalize_numbers() {
+    let mut data = json::parse(r#"{"a":-0.0,"b":{"c":[-0,2.500,100,1e2,-3.0]},"d":"-0.0"}"#).unwrap();
+
+    data.normalize_numbers();
+
+    assert_eq!(data.dump(), r#"{"a":0,"b":{"c":[0,2.5,100,100,-3]},"d":"-0.0"}"#);
+
+    if let JsonValue::Number(ref number) = data["a"] {
+        assert!(number.is_sign_positive());
+    } else {
+        panic!("expected a number");
+    }
+
+    let mut nan = JsonValue::from(std::f64::NAN);
+    nan.normalize_numbers();
+
+    assert!(nan.as_number().unwrap().is_nan());
+}
+
+#[test]
+fn coerce_numeric_strings() {
+    let mut data = object!{
+        a: "42",
+        b: ["3.14", "-1e3", "abc", "", "-"],
+        c: { d: "0", e: " 7", f: "7 ", g: "1.5.2", h: "007", i: "[1]" },
+        "12": true,
+        j: null
+    };
+
+    data.coerce_numeric_strings();
+
+    assert_eq!(data, object!{
+        a: 42,
+        b: [3.14, -1000, "abc", "", "-"],
+        c: { d: 0, e: " 7", f: "7 ", g: "1.5.2", h: "007", i: "[1]" },
+        "12": true,
+        j: null
+    });
+
+    let mut long = JsonValue::from("123456789012345678901234567890123");
+
+    long.coerce_numeric_strings();
+    assert!(long.is_number());
+}
+
+#[test]
+fn array_of_len() {
+    let mut data = JsonValue::array_of_len(3);
+
+    assert_eq!(data.len(), 3);
+    assert!(data.members().all(|member| member.is_null()));
+
+    data[2] = 30.into();
+    data[0] = 10.into();
+
+    assert_eq!(data, array![10, null, 30]);
+    assert_eq!(JsonValue::array_of_len(0), array![]);
+}
+
+#[test]
+fn replace_with() {
+    let mut data = object!{ id: "42", name: "foo" };
+
+    data["id"].replace_with(|value| {
+        let parsed: u32 = value.as_str().unwrap().parse().unwrap();
+        Ok(parsed.into())
+    }).unwrap();
+
+    assert_eq!(data, object!{ id: 42, name: "foo" });
+
+    let result = data["name"].replace_with(|value| json::parse(value.as_str().unwrap()));
+
+    assert!(result.is_err());
+    assert_eq!(data, object!{ id: 42, name: null });
+}
+
+#[test]
+fn contains_subtree() {
+    let data = object!{
+        status: "error",
+        details: {
+            errors: [
+                { code: 1, path: ["a", "b"] },
+                { code: 2, path: [] }
+            ]
+        }
+    };
+
+    assert!(data.contains_subtree(&data.clone()));
+    assert!(data.contains_subtree(&object!{ code: 2, path: [] }));
+    assert!(data.contains_subtree(&array!["a", "b"]));
+    assert!(data.contains_subtree(&JsonValue::from("error")));
+    assert!(data.contains_subtree(&JsonValue::from(1)));
+    assert!(!data.contains_subtree(&object!{ code: 2 }));
+    assert!(!data.contains_subtree(&JsonValue::from("errors")));
+    assert!(!data.contains_subtree(&Null));
+}
+
+#[test]
+fn array_aggregates() {
+    let data = array![1, 2, 3, 4];
+
+    assert_eq!(data.sum(), Ok(10.0));
+    assert_eq!(data.avg(), Ok(2.5));
+    assert_eq!(data.min_number(), Ok(1.0));
+    assert_eq!(data.max_number(), Ok(4.0));
+
+    let data = array![-1.5, 10, 0];
+
+    assert_eq!(data.min_number(), Ok(-1.5));
+    assert_eq!(data.max_number(), Ok(10.0));
+
+    let empty = array![];
+
+    assert_eq!(empty.sum(), Ok(0.0));
+    assert_eq!(empty.avg(), Err(JsonError::EmptyArray));
+    assert_eq!(empty.min_number(), Err(JsonError::EmptyArray));
+    assert_eq!(empty.max_number(), Err(JsonError::EmptyArray));
+    assert_eq!(JsonError::EmptyArray.to_string(), "Array is empty");
+
+    assert_eq!(array![1, "2", 3].sum(), Err(JsonError::wrong_type("Number at index 1")));
+    assert_eq!(array![1, null].max_number(), Err(JsonError::wrong_type("Number at index 1")));
+    assert_eq!(object!{ a: 1 }.sum(), Err(JsonError::wrong_type("Array")));
+}
+
+#[test]
+fn zip_objects() {
+    let left = object!{ a: 1, b: { c: true } };
+    let right = object!{ b: { c: false }, d: [1] };
+
+    let zipped = left.zip(&right).unwrap();
+
+    assert_eq!(zipped, object!{
+        a: [1, null],
+        b: [{ c: true }, { c: false }],
+        d: [null, [1]]
+    });
+    assert_eq!(zipped.dump(), r#"{"a":[1,null],"b":[{"c":true},{"c":false}],"d":[null,[1]]}"#);
+    assert_eq!(object!{}.zip(&object!{}).unwrap(), object!{});
+    assert_eq!(left.zip(&Null), Err(JsonError::wrong_type("Object")));
+}
+
+#[test]
+fn zip_arrays() {
+    let numbers = array![1, 2, 3];
+    let letters = array!["a", "b"];
+
+    assert_eq!(numbers.zip_arrays(&letters).unwrap(), array![[1, "a"], [2, "b"]]);
+    assert_eq!(letters.zip_arrays(&numbers).unwrap(), array![["a", 1], ["b", 2]]);
+    assert_eq!(numbers.zip_arrays(&array![]).unwrap(), array![]);
+    assert_eq!(numbers.zip_arrays(&object!{}), Err(JsonError::wrong_type("Array")));
+    assert_eq!(Null.zip_arrays(&numbers), Err(JsonError::wrong_type("Array")));
+}
+
+#[test]
+fn entries_sorted_by_value() {
+    let data = object!{ a: 3, b: 1, c: 2, d: 1 };
+
+    assert_eq!(data.entries_sorted_by_value().unwrap(), vec![
+        ("b", &JsonValue::from(1)),
+        ("d", &JsonValue::from(1)),
+        ("c", &JsonValue::from(2)),
+        ("a", &JsonValue::from(3)),
+    ]);
+
+    let names = object!{ x: "bob", y: "alice" };
+
+    assert_eq!(names.entries_sorted_by_value().unwrap(), vec![
+        ("y", &JsonValue::from("alice")),
+        ("x", &JsonValue::from("bob")),
+    ]);
+
+    let error = Err(JsonError::wrong_type("Numbers, Strings or Booleans of one kind"));
+
+    assert_eq!(object!{}.entries_sorted_by_value(), Ok(vec![]));
+    assert_eq!(object!{ a: 1, b: "2" }.entries_sorted_by_value(), error);
+    assert_eq!(object!{ a: null }.entries_sorted_by_value(), error);
+    assert_eq!(object!{ a: [1], b: [2] }.entries_sorted_by_value(), error);
+    assert_eq!(object!{ a: 1, b: ::std::f64::NAN }.entries_sorted_by_value(), error);
+
+    let mut mixed = JsonValue::new_object();
+
+    for i in 0..100 {
+        let value: JsonValue = if i == 50 { "x".into() } else { (i % 7).into() };
+        mixed[format!("k{}", i)] = value;
+    }
+
+    assert_eq!(mixed.entries_sorted_by_value(), error);
+    assert_eq!(array![].entries_sorted_by_value(), Err(JsonError::wrong_type("Object")));
+}
+
+#[test]
+fn assert_matches() {
+    let data = object!{
+        id: 42,
+        user: {
+            name: "Jane",
+            "e/mail": "jane@example.com",
+            roles: ["admin", "dev"]
+        },
+        created: "2020-01-01"
+    };
+
+    assert_eq!(data.assert_matches(&object!{}), Ok(()));
+    assert_eq!(data.assert_matches(&object!{ user: { roles: ["admin", null] }, created: null }), Ok(()));
+    assert_eq!(data.assert_matches(&object!{ id: 42, missing: null }), Ok(()));
+    assert_eq!(data.assert_matches(&Null), Ok(()));
+
+    assert_eq!(data.assert_matches(&object!{ user: { "e/mail": "bob@example.com" } }), Err(JsonError::PatternMismatch {
+        pointer: "/user/e~1mail".into(),
+        expected: r#""bob@example.com""#.into(),
+        found: r#""jane@example.com""#.into(),
+    }));
+    assert_eq!(data.assert_matches(&object!{ user: { roles: ["admin"] } }), Err(JsonError::PatternMismatch {
+        pointer: "/user/roles".into(),
+        expected: r#"["admin"]"#.into(),
+        found: r#"["admin","dev"]"#.into(),
+    }));
+    assert_eq!(data.assert_matches(&object!{ user: { roles: [null, "ops"] } }), Err(JsonError::PatternMismatch {
+        pointer: "/user/roles/1".into(),
+        expected: r#""ops""#.into(),
+        found: r#""dev""#.into(),
+    }));
+    assert_eq!(data.assert_matches(&object!{ user: { age: 30 } }), Err(JsonError::undefined("/user/age")));
+    assert_eq!(data.assert_matches(&array![]), Err(JsonError::PatternMismatch {
+        pointer: "".into(),
+        expected: "[]".into(),
+        found: data.dump(),
+    }));
+}
+
+#[test]
+fn to_properties() {
+    let data = object!{
+        name: "app",
+        debug: false,
+        retries: 3,
+        missing: null,
+        empty: {},
+        servers: [
+            { host: "a.example.com", port: 80 },
+            { host: "b.example.com", note: "multi\nline \\ path" }
+        ]
+    };
+
+    assert_eq!(data.to_properties().unwrap(), "\
+name=app
+debug=false
+retries=3
+missing=
+servers.0.host=a.example.com
+servers.0.port=80
+servers.1.host=b.example.com
+servers.1.note=multi\\nline \\\\ path
+");
+
+    assert_eq!(object!{ "a.b" => 1 }.to_properties(), Err(JsonError::invalid_argument(r#"Expected key without `.`, `=` or line breaks, got "a.b""#)));
+    assert_eq!(JsonValue::from(1).to_properties(), Err(JsonError::wrong_type("Object or Array")));
+}
+
+#[test]
+fn properties_round_trip() {
+    let data = object!{
+        name: "app",
+        servers: [
+            { host: "a.example.com", tags: ["x", "y"] },
+            { host: "b.example.com", note: "multi\nline \\ path" }
+        ],
+        "a/b~c": { "": "empty key" }
+    };
+
+    let properties = data.to_properties().unwrap();
+
+    assert_eq!(JsonValue::from_properties(&properties).unwrap(), data);
+}
+
+#[test]
+fn from_properties() {
+    let data = JsonValue::from_properties("# comment\n! also a comment\n\nport=8080\nurl=http://x/?a=b\n").unwrap();
+
+    assert_eq!(data, object!{ port: "8080", url: "http://x/?a=b" });
+    assert_eq!(
+        JsonValue::from_properties("a=1\nb\n"),
+        Err(JsonError::InvalidSyntax { line: 2, expected: "key=value".into() })
+    );
+    assert_eq!(
+        JsonValue::from_properties("a=1\nb\n").unwrap_err().to_string(),
+        "Invalid syntax at line 2, expected: key=value"
+    );
+    assert!(JsonValue::from_properties("a.0=1\na.b=2\n").is_err());
+
+    // Untrusted indexes can't make arrays grow past their end
+    assert_eq!(
+        JsonValue::from_properties("a.18446744073709551615=x"),
+        Err(JsonError::ArrayIndexOutOfBounds { index: usize::MAX, len: 0 })
+    );
+    assert!(JsonValue::from_properties("a.0=x\na.4000000000=y").is_err());
+}
+
+#[test]
+fn total_ordering() {
+    use std::collections::BTreeSet;
+    use std::f64;
+
+    let mut values = vec![
+        object!{ b: 1 },
+        array![2],
+        JsonValue::from("a string that is too long to fit in a short"),
+        JsonValue::from(f64::NAN),
+        JsonValue::from(-1),
+        JsonValue::from(true),
+        object!{ a: 2 },
+        JsonValue::from("a"),
+        JsonValue::from(1e30),
+        object!{ a: 1, z: 0 },
+        array![1, 2],
+        Null,
+        JsonValue::from(0.5),
+        JsonValue::from(false),
+        array![],
+    ];
+
+    values.sort_by(JsonValue::total_cmp);
+
+    assert_eq!(values, vec![
+        Null,
+        JsonValue::from(false),
+        JsonValue::from(true),
+        JsonValue::from(-1),
+        JsonValue::from(0.5),
+        JsonValue::from(1e30),
+        JsonValue::from(f64::NAN),
+        JsonValue::from("a"),
+        JsonValue::from("a string that is too long to fit in a short"),
+        array![],
+        array![1, 2],
+        array![2],
+        object!{ a: 1, z: 0 },
+        object!{ a: 2 },
+        object!{ b: 1 },
+    ]);
+
+    assert_eq!(object!{ a: 1, b: 2 }.total_cmp(&object!{ b: 2, a: 1 }), std::cmp::Ordering::Equal);
+    assert_eq!(JsonValue::from(-0.0).total_cmp(&JsonValue::from(0)), std::cmp::Ordering::Equal);
+    assert!(JsonValue::from(1.5) < JsonValue::from(15));
+    assert!(JsonValue::from(-100) < JsonValue::from(-99.5));
+    assert!(JsonValue::from(123456789012345678u64) < JsonValue::from(123456789012345679u64));
+
+    let set: BTreeSet<JsonValue> = vec![
+        JsonValue::from(1),
+        JsonValue::from(1.0),
+        JsonValue::from(f64::NAN),
+        JsonValue::from(f64::NAN),
+        JsonValue::from("1"),
+    ].into_iter().collect();
+
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(&JsonValue::from(1u8)));
+    assert_eq!(std::cmp::max(JsonValue::from(2), JsonValue::from(10)), 10);
+}
+
+#[test]
+fn raw_number_ordering() {
+    let raw = json::parse_preserve_numbers("[1.0, 1.00, 2]").unwrap();
+
+    assert_eq!(raw[0].total_cmp(&JsonValue::from(1)), std::cmp::Ordering::Equal);
+    assert!(raw[0] < JsonValue::from(1.5));
+    assert_eq!(raw[0].total_cmp(&raw[1]), std::cmp::Ordering::Equal);
+    assert!(raw[1] < raw[2]);
+    assert_eq!(raw[0].total_cmp(&raw[0].clone()), std::cmp::Ordering::Equal);
+}
+
+#[test]
+fn freeze_shares_across_threads() {
+    let frozen = object!{ name: "app", ports: [80, 443] }.freeze();
+    let shared = frozen.clone();
+
+    let handle = std::thread::spawn(move || {
+        (shared["name"].as_str().map(String::from), shared.at(0).is_none(), shared.pointer("/ports/1").cloned())
+    });
+
+    assert_eq!(handle.join().unwrap(), (Some("app".into()), true, Some(JsonValue::from(443))));
+    assert_eq!(frozen.get("ports").unwrap()[0], 80);
+    assert_eq!(frozen, object!{ name: "app", ports: [80, 443] });
+    assert_eq!(frozen.thaw(), object!{ name: "app", ports: [80, 443] });
+}
+
+#[test]
+fn as_unix_seconds() {
+    assert_eq!(JsonValue::from(1700000000).as_unix_seconds(), Ok(1700000000));
+    assert_eq!(JsonValue::from(-1).as_unix_seconds(), Ok(-1));
+    assert_eq!(JsonValue::from(1.5).as_unix_seconds(), Err(JsonError::IntegerOutOfRange("1.5".into())));
+    assert_eq!(JsonValue::from(f64::NAN).as_unix_seconds(), Err(JsonError::NonFiniteNumber));
+
+    assert_eq!(JsonValue::from("1970-01-01T00:00:00Z").as_unix_seconds(), Ok(0));
+    assert_eq!(JsonValue::from("2023-11-14T22:13:20Z").as_unix_seconds(), Ok(1700000000));
+    assert_eq!(JsonValue::from("2023-11-14t17:13:20.999-05:00").as_unix_seconds(), Ok(1700000000));
+    assert_eq!(JsonValue::from("2024-02-29 00:00:00+00:00").as_unix_seconds(), Ok(1709164800));
+    assert_eq!(JsonValue::from("1969-12-31T23:59:59Z").as_unix_seconds(), Ok(-1));
+
+    assert!(JsonValue::from("2023-02-29T00:00:00Z").as_unix_seconds().is_err());
+    assert!(JsonValue::from("2023-11-14T22:13:20").as_unix_seconds().is_err());
+    assert!(JsonValue::from("2023-11-14T22:13:20.Z").as_unix_seconds().is_err());
+    assert!(JsonValue::from("2023-11-14").as_unix_seconds().is_err());
+    assert_eq!(
+        JsonValue::from("yesterday").as_unix_seconds(),
+        Err(JsonError::invalid_argument("Expected RFC 3339 timestamp, got \"yesterday\""))
+    );
+    assert_eq!(Null.as_unix_seconds(), Err(JsonError::wrong_type("Number or String")));
+}
+
+#[test]
+fn validate_schema() {
+    let schema = object!{
+        type: "object",
+        required: ["name", "age"],
+        properties: {
+            name: { type: "string" },
+            age: { type: "integer", minimum: 0, maximum: 150 },
+            role: { enum: ["admin", "user"] },
+            "a/b": { type: ["string", "null"] },
+            tags: { type: "array", minItems: 1, maxItems: 2, items: { type: "string" } }
+        }
+    };
+
+    assert!(object!{ name: "Jane", age: 30 }.validate_schema(&schema).is_ok());
+    assert!(object!{ name: "Jane", age: 30, role: "admin", "a/b": null, tags: ["x"], extra: 1 }.validate_schema(&schema).is_ok());
+
+    let mismatch = |pointer: &str, expected: &str, found: &str| Err(JsonError::PatternMismatch {
+        pointer: pointer.into(),
+        expected: expected.into(),
+        found: found.into(),
+    });
+
+    // Missing required key, reported before the type mismatch of `name`
+    assert_eq!(object!{ name: 1 }.validate_schema(&schema), Err(JsonError::undefined("/age")));
+    assert_eq!(object!{ name: 1, age: 30 }.validate_schema(&schema), mismatch("/name", "string", "integer"));
+    assert_eq!(object!{ name: "Jane", age: 30.5 }.validate_schema(&schema), mismatch("/age", "integer", "number"));
+    assert_eq!(object!{ name: "Jane", age: -1 }.validate_schema(&schema), mismatch("/age", ">= 0", "-1"));
+    assert_eq!(object!{ name: "Jane", age: 200 }.validate_schema(&schema), mismatch("/age", "<= 150", "200"));
+    assert_eq!(object!{ name: "Jane", age: 1, role: "root" }.validate_schema(&schema), mismatch("/role", r#"one of ["admin","user"]"#, r#""root""#));
+    assert_eq!(object!{ name: "Jane", age: 1, "a/b": 1 }.validate_schema(&schema), mismatch("/a~1b", "string or null", "integer"));
+    assert_eq!(object!{ name: "Jane", age: 1, tags: [] }.validate_schema(&schema), mismatch("/tags", "at least 1 items", "0 items"));
+    assert_eq!(object!{ name: "Jane", age: 1, tags: ["a", "b", "c"] }.validate_schema(&schema), mismatch("/tags", "at most 2 items", "3 items"));
+    assert_eq!(object!{ name: "Jane", age: 1, tags: ["a", false] }.validate_schema(&schema), mismatch("/tags/1", "string", "boolean"));
+    assert_eq!(array![].validate_schema(&schema), mismatch("", "object", "array"));
+
+    // Keywords only apply to their own types
+    assert!(JsonValue::from("x").validate_schema(&object!{ minimum: 1, minItems: 1, required: ["a"] }).is_ok());
+    assert!(Null.validate_schema(&object!{}).is_ok());
+
+    assert_eq!(Null.validate_schema(&array![]), Err(JsonError::invalid_argument("Expected Object schema, got []")));
+    assert_eq!(Null.validate_schema(&object!{ type: "thing" }), Err(JsonError::invalid_argument(r#"Expected valid "type" schema keyword, got "thing""#)));
+    assert!(JsonValue::from(1).validate_schema(&object!{ minimum: "0" }).is_err());
+    assert!(array![].validate_schema(&object!{ minItems: -1 }).is_err());
+    assert!(object!{}.validate_schema(&object!{ required: "a" }).is_err());
+}
+
+#[test]
+fn query() {
+    let data = object!{
+        users: [
+            { name: "Jane", email: "jane@example.com", roles: ["admin", "dev"] },
+            { name: "John", roles: [] },
+            { name: "Joe", email: "joe@example.com", roles: ["ops"] },
+            "not a user"
+        ],
+        teams: { a: { lead: "Jane" }, b: { lead: "Joe" } }
+    };
+
+    assert_eq!(data.query("users.*.email"), ["jane@example.com", "joe@example.com"]);
+    assert_eq!(data.query("users.*.roles.*"), ["admin", "dev", "ops"]);
+    assert_eq!(data.query("users.*.roles.0"), ["admin", "ops"]);
+    assert_eq!(data.query("teams.*.lead"), ["Jane", "Joe"]);
+    assert_eq!(data.query("users.2.name"), ["Joe"]);
+    assert_eq!(data.query("users.3"), ["not a user"]);
+    assert_eq!(data.query("*").len(), 2);
+    assert_eq!(data.query(""), [&data]);
+
+    assert!(data.query("users.*.phone").is_empty());
+    assert!(data.query("users.9").is_empty());
+    assert!(data.query("users.01").is_empty());
+    assert!(data.query("teams.0").is_empty());
+    assert!(JsonValue::from(1).query("*").is_empty());
+}